@@ -0,0 +1,219 @@
+//! Types for caching parsed recipe information on disk.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use crate::recipe::recipe_documents;
+use crate::{Recipe, RecipeFormat, SousError, Summary};
+
+/// Name of the cache file stored within a cookbook directory.
+pub const CACHE_FILE: &str = ".sous-cache.json";
+
+/// Format version of the cache file; caches with another version are discarded.
+const CACHE_VERSION: u32 = 2;
+
+/// On-disk cache of recipe [Summary]s for a cookbook directory.
+///
+/// Entries are reused while a recipe file's modification time is unchanged. When the modification
+/// time differs, the file's content hash is compared before parsing it again, so touching a file
+/// without changing it does not cause a reparse.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cache {
+    version: u32,
+    entries: BTreeMap<String, CacheEntry>,
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    modified_secs: u64,
+    modified_nanos: u32,
+    hash: u64,
+    summaries: Vec<Summary>,
+}
+
+impl Cache {
+    /// Load the cache for the given cookbook directory.
+    ///
+    /// A missing, unreadable or outdated cache file results in an empty cache.
+    pub fn load(dir: &Path) -> Self {
+        let path = dir.join(CACHE_FILE);
+
+        let mut cache = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Cache>(&content).ok())
+            .filter(|cache| cache.version == CACHE_VERSION)
+            .unwrap_or_default();
+
+        cache.version = CACHE_VERSION;
+        cache.path = path;
+        cache
+    }
+
+    /// Get the [Summary] of every recipe in the given file, parsing it only if it has changed.
+    ///
+    /// A YAML file of several `---` separated recipes has one summary per document, in order.
+    pub fn summaries(&mut self, dir: &Path, file: &str) -> Result<Vec<Summary>, SousError> {
+        let path = dir.join(file);
+        let (secs, nanos) = modified(&path)?;
+
+        if let Some(entry) = self.entries.get(file) {
+            if entry.modified_secs == secs && entry.modified_nanos == nanos {
+                return Ok(entry.summaries.clone());
+            }
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let hash = fnv1a(content.as_bytes());
+        let summaries = match self.entries.get(file) {
+            Some(entry) if entry.hash == hash => entry.summaries.clone(),
+            _ => {
+                let format = RecipeFormat::from_path(&path);
+                recipe_documents(&content, format)
+                    .into_iter()
+                    .map(|document| Ok(Summary::new(file, &Recipe::from_format(document, format)?)))
+                    .collect::<Result<_, SousError>>()?
+            }
+        };
+
+        self.entries.insert(
+            file.to_string(),
+            CacheEntry {
+                modified_secs: secs,
+                modified_nanos: nanos,
+                hash,
+                summaries: summaries.clone(),
+            },
+        );
+        self.dirty = true;
+
+        Ok(summaries)
+    }
+
+    /// Get the number of recipes in the given file, if it is cached and unmodified since.
+    ///
+    /// Unlike [Cache::summaries], the file is not read.
+    pub fn documents(&self, dir: &Path, file: &str) -> Option<usize> {
+        let entry = self.entries.get(file)?;
+        let (secs, nanos) = modified(&dir.join(file)).ok()?;
+
+        (entry.modified_secs == secs && entry.modified_nanos == nanos)
+            .then_some(entry.summaries.len())
+    }
+
+    /// Drop entries for files not in the provided list.
+    pub fn retain(&mut self, files: &[String]) {
+        let before = self.entries.len();
+        self.entries.retain(|file, _| files.contains(file));
+
+        if self.entries.len() != before {
+            self.dirty = true;
+        }
+    }
+
+    /// Write the cache back to disk if it has changed since loading.
+    pub fn save(&mut self) -> Result<(), SousError> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let temp = self.path.with_extension("tmp");
+        fs::write(&temp, serde_json::to_string(self)?)?;
+        fs::rename(&temp, &self.path)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// Modification time of a file, as seconds and nanoseconds since the Unix epoch.
+fn modified(path: &Path) -> Result<(u64, u32), SousError> {
+    let modified = fs::metadata(path)?
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    Ok((modified.as_secs(), modified.subsec_nanos()))
+}
+
+/// 64-bit FNV-1a hash, stable across platforms and compiler versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::temp_dir;
+
+    fn gen_recipe(name: &str) -> String {
+        format!(
+            "name: {name}\nauthor: test\nservings: 1\ncook_minutes: 1\nsteps: []\ningredients: []"
+        )
+    }
+
+    #[test]
+    fn test_summary_cached() -> Result<(), SousError> {
+        let dir = temp_dir("cache");
+        fs::write(dir.join("a.yml"), gen_recipe("first"))?;
+
+        let mut cache = Cache::load(&dir);
+        assert_eq!(cache.documents(&dir, "a.yml"), None);
+        assert_eq!(cache.summaries(&dir, "a.yml")?[0].metadata.name, "first");
+        cache.save()?;
+        assert!(dir.join(CACHE_FILE).is_file());
+
+        let mut cache = Cache::load(&dir);
+        assert_eq!(cache.entries.len(), 1);
+        assert_eq!(cache.documents(&dir, "a.yml"), Some(1));
+        assert_eq!(cache.summaries(&dir, "a.yml")?[0].metadata.name, "first");
+        assert!(!cache.dirty);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_summary_changed() -> Result<(), SousError> {
+        let dir = temp_dir("cache-changed");
+        fs::write(dir.join("a.yml"), gen_recipe("first"))?;
+
+        let mut cache = Cache::load(&dir);
+        cache.summaries(&dir, "a.yml")?;
+
+        fs::write(
+            dir.join("a.yml"),
+            [gen_recipe("second"), gen_recipe("third")].join("\n---\n"),
+        )?;
+        cache.entries.get_mut("a.yml").unwrap().modified_secs = 0;
+        assert_eq!(cache.documents(&dir, "a.yml"), None);
+        let summaries = cache.summaries(&dir, "a.yml")?;
+        assert_eq!(summaries[0].metadata.name, "second");
+        assert_eq!(summaries[1].metadata.name, "third");
+        assert_eq!(cache.documents(&dir, "a.yml"), Some(2));
+
+        cache.retain(&[]);
+        assert!(cache.entries.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_corrupt() -> Result<(), SousError> {
+        let dir = temp_dir("cache-corrupt");
+        fs::write(dir.join(CACHE_FILE), "not json")?;
+
+        let cache = Cache::load(&dir);
+        assert!(cache.entries.is_empty());
+
+        Ok(())
+    }
+}
@@ -1,41 +1,200 @@
 //! Types for managing collections of recipes.
 
 use std::{
-    fs::read_dir,
+    collections::BTreeMap,
+    fs::{self, create_dir_all, read_dir, read_to_string, File},
     path::{Path, PathBuf},
 };
 
-use crate::{Recipe, SousError};
+use serde_yaml::Value;
+
+use crate::cache::{Cache, CACHE_FILE};
+use crate::format::slugify;
+use crate::index::{Index, IndexEntry, IndexSection};
+use crate::manifest::{Manifest, MANIFEST_FILE};
+use crate::recipe::recipe_documents;
+use crate::render::Labels;
+use crate::{Filter, Query, Recipe, RecipeFormat, SearchResult, SousError, Summary};
 
 /// Directory of recipe files.
 ///
-/// Stores both the directory's path on the filesystem and a list of found YAML files.
+/// Stores both the directory's path on the filesystem and a list of found recipe files in any
+/// [RecipeFormat]. If the directory contains a `cookbook.yml` [Manifest], recipes are ordered by
+/// its chapters, followed by any remaining recipes in alphabetical order.
+///
+/// A YAML file of several `---` separated recipes within the directory holds one recipe per
+/// document, named by the file's stem and the document's number (e.g. `menu-2.yml`). Documents
+/// are only parsed when their recipe is loaded, and the number of documents in a file is taken
+/// from the cache file, if present, while the file is unmodified.
+///
+/// A cookbook may also hold recipes in memory, such as those of a multi-document YAML file (see
+/// [Cookbook::from_recipes]).
 pub struct Cookbook {
     path: PathBuf,
+    manifest: Manifest,
     recipes: Vec<String>,
+    documents: Option<Vec<Recipe>>,
+    streams: BTreeMap<String, (String, usize)>,
+    cached: bool,
 }
 
 impl Cookbook {
-    /// Open a cookbook directory.
+    /// Open a cookbook directory, or a YAML file of `---` separated recipes.
     pub fn open(path: &Path) -> Result<Cookbook, SousError> {
+        if path.is_file() {
+            let recipes = Recipe::from_reader(File::open(path)?, RecipeFormat::from_path(path))?;
+            return Ok(Cookbook {
+                path: path.to_path_buf(),
+                ..Cookbook::from_recipes(recipes)
+            });
+        }
+
         let dir = read_dir(path)?.filter(|entry| match entry {
             Ok(entry) => match entry.path().extension() {
-                Some(extension) => extension == "yml",
+                Some(extension) => {
+                    let name = entry.file_name();
+                    extension
+                        .to_str()
+                        .and_then(RecipeFormat::from_extension)
+                        .is_some()
+                        && name != MANIFEST_FILE
+                        && name != CACHE_FILE
+                        && !name.to_string_lossy().starts_with('.')
+                }
                 None => false,
             },
             Err(_) => true,
         });
 
-        let mut recipes: Vec<String> = Vec::new();
+        let mut found: Vec<String> = Vec::new();
         for entry in dir {
-            recipes.push(match entry?.file_name().into_string() {
+            found.push(match entry?.file_name().into_string() {
                 Ok(filename) => filename,
                 Err(_) => return Err(SousError::Unknown),
             });
         }
+        found.sort();
+
+        let cache = Cache::load(path);
+        let mut names: Vec<String> = Vec::new();
+        let mut streams = BTreeMap::new();
+        for file in found.iter() {
+            let documents = cache
+                .documents(path, file)
+                .unwrap_or_else(|| count_documents(&path.join(file)));
+            if documents < 2 {
+                names.push(file.clone());
+                continue;
+            }
+
+            let (stem, extension) = file.rsplit_once('.').unwrap_or((file, ""));
+            for i in 0..documents {
+                let mut name = format!("{stem}-{}.{extension}", i + 1);
+                let mut count = 1;
+                while found.contains(&name) || names.contains(&name) {
+                    count += 1;
+                    name = format!("{stem}-{}-{count}.{extension}", i + 1);
+                }
+                names.push(name.clone());
+                streams.insert(name, (file.clone(), i));
+            }
+        }
+
+        let manifest_path = path.join(MANIFEST_FILE);
+        let mut manifest = if manifest_path.is_file() {
+            Manifest::from_file(&manifest_path)?
+        } else {
+            Manifest::new()
+        };
+
+        let mut recipes: Vec<String> = Vec::new();
+        for chapter in manifest.chapters.iter_mut() {
+            let mut resolved: Vec<String> = Vec::new();
+            for entry in chapter.recipes.iter() {
+                let matching: Vec<&String> = match names.iter().find(|f| is_recipe_file(f, entry)) {
+                    Some(name) => vec![name],
+                    None => names
+                        .iter()
+                        .filter(|name| {
+                            streams
+                                .get(*name)
+                                .is_some_and(|(file, _)| is_recipe_file(file, entry))
+                        })
+                        .collect(),
+                };
+                if matching.is_empty() {
+                    return Err(SousError::MissingRecipe(entry.clone()));
+                }
+                resolved.extend(matching.into_iter().cloned());
+            }
+
+            for name in resolved.iter() {
+                if !recipes.contains(name) {
+                    recipes.push(name.clone());
+                }
+            }
+            chapter.recipes = resolved;
+        }
+        for name in names {
+            if !recipes.contains(&name) {
+                recipes.push(name);
+            }
+        }
+
         let path = path.to_path_buf();
 
-        Ok(Cookbook { path, recipes })
+        Ok(Cookbook {
+            path,
+            manifest,
+            recipes,
+            documents: None,
+            streams,
+            cached: false,
+        })
+    }
+
+    /// Create a cookbook of recipes held in memory, without a manifest.
+    ///
+    /// Recipes keep their order and are named by their [slugified](slugify) names, e.g.
+    /// `apple-pie`, with a number appended to repeated names.
+    pub fn from_recipes(recipes: Vec<Recipe>) -> Cookbook {
+        let mut names: Vec<String> = Vec::new();
+        for recipe in recipes.iter() {
+            let slug = match slugify(&recipe.metadata.name.to_string()) {
+                slug if slug.is_empty() => "recipe".to_string(),
+                slug => slug,
+            };
+            let mut name = slug.clone();
+            let mut count = 1;
+            while names.contains(&name) {
+                count += 1;
+                name = format!("{slug}-{count}");
+            }
+            names.push(name);
+        }
+
+        Cookbook {
+            path: PathBuf::new(),
+            manifest: Manifest::new(),
+            recipes: names,
+            documents: Some(recipes),
+            streams: BTreeMap::new(),
+            cached: false,
+        }
+    }
+
+    /// Keep recipe [Summary]s in a cache file within the cookbook directory.
+    ///
+    /// Listing, filtering and searching a cached cookbook only parses recipe files that changed
+    /// since the cache was last updated. Cookbooks of recipes held in memory are never cached.
+    pub fn with_cache(mut self) -> Self {
+        self.cached = true;
+        self
+    }
+
+    /// Get the path of the cookbook directory, or of the file holding its recipes.
+    pub fn path(&self) -> &Path {
+        &self.path
     }
 
     /// Get a borrowed [Vec] of available recipe names.
@@ -43,8 +202,751 @@ impl Cookbook {
         &self.recipes
     }
 
+    /// Get the names of the recipe files in the cookbook directory, in order.
+    ///
+    /// Unlike [Cookbook::recipes], a file holding several recipes is listed once, by its own name.
+    /// Cookbooks of recipes held in memory have no files.
+    pub fn files(&self) -> Vec<String> {
+        if self.documents.is_some() {
+            return Vec::new();
+        }
+
+        let mut files: Vec<String> = Vec::new();
+        for name in self.recipes.iter() {
+            let file = match self.streams.get(name) {
+                Some((file, _)) => file,
+                None => name,
+            };
+            if !files.contains(file) {
+                files.push(file.clone());
+            }
+        }
+
+        files
+    }
+
+    /// Get the file name of the page a recipe is rendered to, e.g. `pancakes.html`.
+    ///
+    /// The recipe's file extension is replaced with `extension`. As `index` names the cookbook's
+    /// [Index] page, a recipe named `index` is rendered to `index-1` instead, or the first such
+    /// name not taken by another recipe.
+    pub fn page(&self, file: &str, extension: &str) -> String {
+        let stem = |file: &str| Path::new(file).with_extension("");
+        let mut page = stem(file);
+        let mut count = 0;
+        while page == Path::new(INDEX_PAGE)
+            || (count > 0 && self.recipes.iter().any(|other| stem(other) == page))
+        {
+            count += 1;
+            page = PathBuf::from(format!("{INDEX_PAGE}-{count}"));
+        }
+
+        page.with_extension(extension)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Get the cookbook's [Manifest].
+    ///
+    /// Recipe entries in the manifest's chapters are resolved to file names as returned by
+    /// [Cookbook::recipes]. Cookbooks without a `cookbook.yml` have an empty manifest.
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Get the path of the manifest's default template, if any.
+    pub fn default_template(&self) -> Option<PathBuf> {
+        self.manifest
+            .defaults
+            .template
+            .as_ref()
+            .map(|template| self.path.join(template))
+    }
+
     /// Load a [Recipe] matching the given name.
     pub fn load_recipe(&self, name: &str) -> Result<Recipe, SousError> {
-        Ok(Recipe::from_file(&self.path.join(name))?)
+        if let Some((file, i)) = self.streams.get(name) {
+            let content = read_to_string(self.path.join(file))?;
+            return match recipe_documents(&content, RecipeFormat::Yaml).get(*i) {
+                Some(document) => Recipe::from_yaml(document),
+                None => Err(SousError::MissingRecipe(name.to_string())),
+            };
+        }
+
+        match &self.documents {
+            Some(documents) => self
+                .recipes
+                .iter()
+                .position(|recipe| recipe == name)
+                .map(|i| documents[i].clone())
+                .ok_or_else(|| SousError::MissingRecipe(name.to_string())),
+            None => Recipe::from_file(&self.path.join(name)),
+        }
+    }
+
+    /// Load a [Recipe] by file name, with or without its extension.
+    ///
+    /// Returns [SousError::MissingRecipe] if the cookbook has no such recipe.
+    pub fn find_recipe(&self, name: &str) -> Result<Recipe, SousError> {
+        let file = self
+            .recipes
+            .iter()
+            .find(|f| is_recipe_file(f, name))
+            .ok_or_else(|| SousError::MissingRecipe(name.to_string()))?;
+
+        self.load_recipe(file)
+    }
+
+    /// Write every recipe into the `output` directory in the given [RecipeFormat], creating it if
+    /// necessary.
+    ///
+    /// Recipes keep their file names with the format's extension, and the manifest's chapters list
+    /// the converted files. Other manifest fields are kept, but comments in it are lost.
+    pub fn convert(&self, output: &Path, format: RecipeFormat) -> Result<(), SousError> {
+        let converted = |file: &str| {
+            Path::new(file)
+                .with_extension(format.extension())
+                .to_string_lossy()
+                .into_owned()
+        };
+        create_dir_all(output)?;
+
+        for file in self.recipes.iter() {
+            let recipe = self.load_recipe(file)?;
+            fs::write(output.join(converted(file)), recipe.to_format(format)?)?;
+        }
+
+        let manifest_path = self.path.join(MANIFEST_FILE);
+        if self.documents.is_none() && manifest_path.is_file() {
+            let mut manifest: Value = serde_yaml::from_str(&read_to_string(&manifest_path)?)?;
+            let chapters = manifest["chapters"].as_sequence_mut();
+            for (chapter, resolved) in chapters.into_iter().flatten().zip(&self.manifest.chapters) {
+                if chapter["recipes"].is_sequence() {
+                    chapter["recipes"] = resolved.recipes.iter().map(|f| converted(f)).collect();
+                }
+            }
+            fs::write(
+                output.join(MANIFEST_FILE),
+                serde_yaml::to_string(&manifest)?,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Build an [Index] of every recipe in the cookbook.
+    ///
+    /// Recipes are grouped by the manifest's chapters, with any remaining recipes collected in a
+    /// final section titled from `labels`. Entries link to the recipe's file name with the given
+    /// extension, and name recipes in `language`, defaulting to each recipe's own language.
+    pub fn index(
+        &self,
+        extension: &str,
+        language: Option<&str>,
+        labels: &Labels,
+    ) -> Result<Index, SousError> {
+        let entry = |file: &String| self.index_entry(file, extension, language);
+
+        let mut sections: Vec<IndexSection> = Vec::new();
+        for chapter in self.manifest.chapters.iter() {
+            sections.push(IndexSection {
+                title: chapter.title.clone(),
+                entries: chapter
+                    .recipes
+                    .iter()
+                    .map(entry)
+                    .collect::<Result<_, _>>()?,
+            });
+        }
+
+        let remaining: Vec<IndexEntry> = self
+            .recipes
+            .iter()
+            .filter(|file| {
+                !self
+                    .manifest
+                    .chapters
+                    .iter()
+                    .any(|chapter| chapter.recipes.contains(file))
+            })
+            .map(entry)
+            .collect::<Result<_, _>>()?;
+        if !remaining.is_empty() {
+            let title = if sections.is_empty() {
+                &labels.recipes
+            } else {
+                &labels.other_recipes
+            };
+            sections.push(IndexSection {
+                title: title.clone(),
+                entries: remaining,
+            });
+        }
+
+        Ok(Index {
+            title: self.manifest.title.clone(),
+            author: self.manifest.author.clone(),
+            description: self.manifest.description.clone(),
+            sections,
+        })
+    }
+
+    /// Build an [Index] of every recipe in the cookbook, grouped by tag.
+    ///
+    /// Sections are sorted by tag name, and recipes with several tags appear in each matching
+    /// section. Tags of the same [slug](slugify), such as "Gluten Free" and "gluten-free", share
+    /// one section titled by the first spelling found, as tag pages are named by slug. Recipes
+    /// without tags are collected in a final section titled from `labels`. Recipes are named as in
+    /// [Cookbook::index].
+    pub fn tag_index(
+        &self,
+        extension: &str,
+        language: Option<&str>,
+        labels: &Labels,
+    ) -> Result<Index, SousError> {
+        let mut tags: BTreeMap<String, IndexSection> = BTreeMap::new();
+        let mut untagged: Vec<IndexEntry> = Vec::new();
+
+        for file in self.recipes.iter() {
+            let entry = self.index_entry(file, extension, language)?;
+            if entry.tags.is_empty() {
+                untagged.push(entry);
+                continue;
+            }
+            for tag in entry.tags.iter() {
+                let section = tags.entry(slugify(tag)).or_insert_with(|| IndexSection {
+                    title: tag.clone(),
+                    entries: Vec::new(),
+                });
+                if !section.entries.iter().any(|e| e.file == entry.file) {
+                    section.entries.push(entry.clone());
+                }
+            }
+        }
+
+        let mut sections: Vec<IndexSection> = tags.into_values().collect();
+        if !untagged.is_empty() {
+            sections.push(IndexSection {
+                title: labels.untagged.clone(),
+                entries: untagged,
+            });
+        }
+
+        Ok(Index {
+            title: self.manifest.title.clone(),
+            author: self.manifest.author.clone(),
+            description: self.manifest.description.clone(),
+            sections,
+        })
+    }
+
+    /// Get the names of recipes whose [Metadata](crate::Metadata) satisfies the given [Filter].
+    pub fn filter(&self, filter: &Filter) -> Result<Vec<String>, SousError> {
+        Ok(self
+            .summaries()?
+            .into_iter()
+            .filter(|summary| filter.matches(&summary.metadata))
+            .map(|summary| summary.file)
+            .collect())
+    }
+
+    /// Load a [Summary] of every recipe in the cookbook, in order.
+    ///
+    /// Summaries are read from the cache file if enabled with [Cookbook::with_cache]. Returns
+    /// [SousError::RecipeError] naming the first recipe that cannot be loaded.
+    pub fn summaries(&self) -> Result<Vec<Summary>, SousError> {
+        self.summary_results()?.into_iter().collect()
+    }
+
+    /// Load a [Summary] of every recipe in the cookbook like [Cookbook::summaries], keeping the
+    /// [SousError::RecipeError] of each recipe that cannot be loaded in its place, so that the
+    /// remaining recipes can still be used.
+    ///
+    /// Only fails if the cache file cannot be written.
+    pub fn summary_results(&self) -> Result<Vec<Result<Summary, SousError>>, SousError> {
+        let named = |name: &String, result: Result<Summary, SousError>| {
+            result.map_err(|e| SousError::RecipeError(name.clone(), Box::new(e)))
+        };
+
+        if !self.cached || self.documents.is_some() {
+            return Ok(self
+                .recipes
+                .iter()
+                .map(|file| {
+                    let summary = self.load_recipe(file).map(|r| Summary::new(file, &r));
+                    named(file, summary)
+                })
+                .collect());
+        }
+
+        let mut cache = Cache::load(&self.path);
+        let summaries = self
+            .recipes
+            .iter()
+            .map(|name| {
+                let (file, i) = match self.streams.get(name) {
+                    Some((file, i)) => (file, *i),
+                    None => (name, 0),
+                };
+                let summary = cache.summaries(&self.path, file).and_then(|summaries| {
+                    summaries
+                        .into_iter()
+                        .nth(i)
+                        .ok_or_else(|| SousError::MissingRecipe(name.clone()))
+                });
+                let summary = summary.map(|summary| Summary {
+                    file: name.clone(),
+                    ..summary
+                });
+                named(name, summary)
+            })
+            .collect();
+
+        cache.retain(&self.files());
+        cache.save()?;
+        Ok(summaries)
+    }
+
+    /// Search the cookbook for recipes matching the given [Query].
+    ///
+    /// Results are ordered by descending relevance.
+    pub fn search(&self, query: &Query) -> Result<Vec<SearchResult>, SousError> {
+        Ok(query.search_all(&self.summaries()?))
+    }
+
+    fn index_entry(
+        &self,
+        file: &str,
+        extension: &str,
+        language: Option<&str>,
+    ) -> Result<IndexEntry, SousError> {
+        let recipe = self.load_recipe(file)?;
+        let link = self.page(file, extension);
+
+        Ok(IndexEntry {
+            file: file.to_string(),
+            ..IndexEntry::new(&recipe, link, language)
+        })
+    }
+}
+
+/// Name of the cookbook's index page, without an extension.
+const INDEX_PAGE: &str = "index";
+
+/// Count the recipes in a file without parsing them.
+///
+/// Only YAML files may hold several recipes. Unreadable files count as a single recipe, failing
+/// when it is loaded.
+fn count_documents(path: &Path) -> usize {
+    let format = RecipeFormat::from_path(path);
+    if format != RecipeFormat::Yaml {
+        return 1;
+    }
+
+    read_to_string(path)
+        .map(|content| recipe_documents(&content, format).len())
+        .unwrap_or(1)
+}
+
+/// Whether `file` is the recipe file `name`, which may omit a recipe file extension.
+fn is_recipe_file(file: &str, name: &str) -> bool {
+    file == name
+        || file.rsplit_once('.').is_some_and(|(stem, extension)| {
+            stem == name && RecipeFormat::from_extension(extension).is_some()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{temp_dir, TempDir};
+    use crate::Locale;
+    use std::fs;
+
+    fn gen_cookbook(name: &str, manifest: Option<&str>) -> TempDir {
+        let path = temp_dir(name);
+
+        for (recipe, tags) in [("a", "[x, y]"), ("b", "[y]"), ("c", "[]")] {
+            let yaml = format!(
+                "name: {recipe}\nauthor: test\nservings: 1\ncook_minutes: 1\ntags: {tags}\nsteps: []\ningredients: []"
+            );
+            fs::write(path.join(recipe).with_extension("yml"), yaml).unwrap();
+        }
+        if let Some(manifest) = manifest {
+            fs::write(path.join(MANIFEST_FILE), manifest).unwrap();
+        }
+
+        path
+    }
+
+    #[test]
+    fn test_open_without_manifest() -> Result<(), SousError> {
+        let path = gen_cookbook("no-manifest", None);
+        let cookbook = Cookbook::open(&path)?;
+
+        assert_eq!(cookbook.recipes(), &vec!["a.yml", "b.yml", "c.yml"]);
+        assert_eq!(cookbook.manifest(), &Manifest::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_with_manifest() -> Result<(), SousError> {
+        let manifest = "
+title: test cookbook
+chapters:
+  - title: first
+    recipes: [c, a.yml]
+defaults:
+  template: template.md
+        ";
+        let path = gen_cookbook("manifest", Some(manifest));
+        let cookbook = Cookbook::open(&path)?;
+
+        assert_eq!(cookbook.recipes(), &vec!["c.yml", "a.yml", "b.yml"]);
+        assert_eq!(
+            cookbook.manifest().chapters[0].recipes,
+            vec!["c.yml", "a.yml"]
+        );
+        assert_eq!(cookbook.default_template(), Some(path.join("template.md")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_recipe() -> Result<(), SousError> {
+        let path = gen_cookbook("find", None);
+        let cookbook = Cookbook::open(&path)?;
+
+        assert_eq!(cookbook.find_recipe("a")?.metadata.name, "a");
+        assert_eq!(cookbook.find_recipe("b.yml")?.metadata.name, "b");
+        assert!(matches!(
+            cookbook.find_recipe("d"),
+            Err(SousError::MissingRecipe(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_yaml_stream() -> Result<(), SousError> {
+        let dir = temp_dir("stream");
+        let path = dir.join("stream.yml");
+        let recipe = |name: &str| {
+            format!("name: {name}\nauthor: test\nservings: 1\ncook_minutes: 1\ningredients: []\nsteps: []\n")
+        };
+        fs::write(
+            &path,
+            [recipe("Apple Pie"), recipe("Soup"), recipe("Apple Pie")].join("---\n"),
+        )?;
+        let cookbook = Cookbook::open(&path)?.with_cache();
+
+        assert_eq!(
+            cookbook.recipes(),
+            &vec!["apple-pie", "soup", "apple-pie-2"]
+        );
+        assert_eq!(cookbook.find_recipe("soup")?.metadata.name, "Soup");
+        assert_eq!(cookbook.summaries()?.len(), 3);
+        assert_eq!(
+            cookbook.index("md", None, &Labels::default())?.sections[0].entries[2].link,
+            "apple-pie-2.md"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_yaml_stream_in_dir() -> Result<(), SousError> {
+        let manifest = "chapters:\n  - title: menu\n    recipes: [menu, a]\n";
+        let path = gen_cookbook("stream-dir", Some(manifest));
+        let recipe = |name: &str| {
+            format!("name: {name}\nauthor: test\nservings: 1\ncook_minutes: 1\ningredients: []\nsteps: []\n")
+        };
+        fs::write(
+            path.join("menu.yml"),
+            [recipe("Soup"), recipe("Stew")].join("---\n"),
+        )?;
+        fs::write(path.join("single.yml"), format!("---\n{}", recipe("Salad")))?;
+        let cookbook = Cookbook::open(&path)?.with_cache();
+
+        assert_eq!(
+            cookbook.recipes(),
+            &vec![
+                "menu-1.yml",
+                "menu-2.yml",
+                "a.yml",
+                "b.yml",
+                "c.yml",
+                "single.yml"
+            ]
+        );
+        assert_eq!(
+            cookbook.files(),
+            vec!["menu.yml", "a.yml", "b.yml", "c.yml", "single.yml"]
+        );
+        assert_eq!(cookbook.find_recipe("menu-2")?.metadata.name, "Stew");
+        assert_eq!(cookbook.find_recipe("single")?.metadata.name, "Salad");
+        assert_eq!(cookbook.summaries()?[1].metadata.name, "Stew");
+        assert_eq!(
+            cookbook.index("md", None, &Labels::default())?.sections[0].entries[1].link,
+            "menu-2.md"
+        );
+
+        let output = path.join("converted");
+        cookbook.convert(&output, RecipeFormat::Json)?;
+        let converted = Cookbook::open(&output)?;
+        assert_eq!(
+            converted.manifest().chapters[0].recipes,
+            vec!["menu-1.json", "menu-2.json", "a.json"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_malformed_stream() -> Result<(), SousError> {
+        let path = gen_cookbook("malformed-stream", None);
+        let recipe =
+            "name: Soup\nauthor: test\nservings: 1\ncook_minutes: 1\ningredients: []\nsteps: []\n";
+        fs::write(path.join("menu.yml"), format!("{recipe}---\nname: [Stew\n"))?;
+        let cookbook = Cookbook::open(&path)?;
+
+        assert!(cookbook.recipes().contains(&"menu-2.yml".to_string()));
+        assert_eq!(cookbook.find_recipe("menu-1")?.metadata.name, "Soup");
+        assert!(cookbook.find_recipe("menu-2").is_err());
+        assert_eq!(cookbook.find_recipe("a")?.metadata.name, "a");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_summary_results() -> Result<(), SousError> {
+        let path = gen_cookbook("summary-results", None);
+        fs::write(path.join("bad.yml"), "name: [Stew\n")?;
+
+        for cookbook in [Cookbook::open(&path)?, Cookbook::open(&path)?.with_cache()] {
+            let results = cookbook.summary_results()?;
+            assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 3);
+
+            let error = cookbook.summaries().unwrap_err();
+            assert!(matches!(&error, SousError::RecipeError(file, _) if file == "bad.yml"));
+            assert!(error.to_string().starts_with("invalid recipe bad.yml: "));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_formats() -> Result<(), SousError> {
+        let path = gen_cookbook(
+            "formats",
+            Some("chapters:\n  - title: Mains\n    recipes: [d]"),
+        );
+        fs::write(
+            path.join("d.json"),
+            r#"{"name": "d", "author": "x", "servings": 1, "cook_minutes": 1,
+                "ingredients": [], "steps": []}"#,
+        )?;
+        fs::write(
+            path.join("e.toml"),
+            "name = \"e\"\nauthor = \"x\"\nservings = 1\ncook_minutes = 1\n\
+             ingredients = []\nsteps = []\n",
+        )?;
+        fs::write(path.join("notes.txt"), "not a recipe")?;
+        let cookbook = Cookbook::open(&path)?;
+
+        assert_eq!(
+            cookbook.recipes(),
+            &vec!["d.json", "a.yml", "b.yml", "c.yml", "e.toml"]
+        );
+        assert_eq!(cookbook.find_recipe("d")?.metadata.name, "d");
+        assert_eq!(cookbook.find_recipe("e")?.metadata.name, "e");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert() -> Result<(), SousError> {
+        let path = gen_cookbook(
+            "convert",
+            Some("title: Test\nchapters:\n  - title: First\n    recipes: [c, a.yml]"),
+        );
+        fs::write(
+            path.join("d.yml"),
+            "name: d\nauthor: test\nservings: 1\ncook_minutes: 1\nsteps: []\n\
+             ingredients:\n  - name: saffron\n    amount: 0.0004\n    unit: g\n",
+        )?;
+        let cookbook = Cookbook::open(&path)?;
+        let output = path.join("json");
+        cookbook.convert(&output, RecipeFormat::Json)?;
+
+        let converted = Cookbook::open(&output)?;
+        assert_eq!(
+            converted.recipes(),
+            &vec!["c.json", "a.json", "b.json", "d.json"]
+        );
+        assert_eq!(converted.manifest().title.as_deref(), Some("Test"));
+        assert_eq!(converted.find_recipe("d")?, cookbook.find_recipe("d")?);
+
+        let back = path.join("yaml");
+        converted.convert(&back, RecipeFormat::Yaml)?;
+        for file in cookbook.recipes() {
+            assert_eq!(
+                Cookbook::open(&back)?.load_recipe(file)?,
+                cookbook.load_recipe(file)?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index() -> Result<(), SousError> {
+        let manifest = "
+title: test cookbook
+chapters:
+  - title: first
+    recipes: [b]
+        ";
+        let path = gen_cookbook("index", Some(manifest));
+        let index = Cookbook::open(&path)?.index("md", None, &Labels::default())?;
+
+        assert_eq!(index.title.as_deref(), Some("test cookbook"));
+        assert_eq!(index.sections.len(), 2);
+        assert_eq!(index.sections[0].title, "first");
+        assert_eq!(index.sections[0].entries[0].name, "b");
+        assert_eq!(index.sections[0].entries[0].link, "b.md");
+        assert_eq!(index.sections[1].title, "Other Recipes");
+        assert_eq!(index.sections[1].entries.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_localized() -> Result<(), SousError> {
+        let path = gen_cookbook("index-localized", None);
+        let yaml = "name: {de: A, fr: B}\nlanguage: fr\nauthor: test\nservings: 1\ncook_minutes: 1\nsteps: []\ningredients: []";
+        fs::write(path.join("a.yml"), yaml)?;
+        let cookbook = Cookbook::open(&path)?;
+
+        let index = cookbook.index("md", None, &Labels::default())?;
+        assert_eq!(index.sections[0].entries[0].name, "B");
+        let index = cookbook.index("md", Some("de"), &Locale::German.labels())?;
+        assert_eq!(index.sections[0].title, "Rezepte");
+        assert_eq!(index.sections[0].entries[0].name, "A");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tag_index() -> Result<(), SousError> {
+        let path = gen_cookbook("tag-index", None);
+        let index = Cookbook::open(&path)?.tag_index("md", None, &Labels::default())?;
+
+        let titles: Vec<&str> = index.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["x", "y", "Untagged"]);
+        assert_eq!(index.sections[1].entries.len(), 2);
+        assert_eq!(index.sections[2].entries[0].name, "c");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tag_index_slugs() -> Result<(), SousError> {
+        let path = gen_cookbook("tag-index-slugs", None);
+        let yaml =
+            "name: d\nauthor: test\nservings: 1\ncook_minutes: 1\nsteps: []\ningredients: []";
+        fs::write(path.join("d.yml"), format!("{yaml}\ntags: [Y, X]"))?;
+        let index = Cookbook::open(&path)?.tag_index("md", None, &Labels::default())?;
+
+        let titles: Vec<&str> = index.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["x", "y", "Untagged"]);
+        assert_eq!(index.sections[0].entries.len(), 2);
+        assert_eq!(index.sections[1].entries.len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_page() -> Result<(), SousError> {
+        let path = gen_cookbook("page", None);
+        let yaml =
+            "name: index\nauthor: test\nservings: 1\ncook_minutes: 1\nsteps: []\ningredients: []";
+        fs::write(path.join("index.yml"), yaml)?;
+        fs::write(
+            path.join("index-1.json"),
+            serde_json::to_string(&Recipe::from_yaml(yaml)?)?,
+        )?;
+        let cookbook = Cookbook::open(&path)?;
+
+        assert_eq!(cookbook.page("a.yml", "md"), "a.md");
+        assert_eq!(cookbook.page("index.yml", "md"), "index-2.md");
+        assert_eq!(cookbook.page("index-1.json", "md"), "index-1.md");
+        let links: Vec<String> = cookbook.index("html", None, &Labels::default())?.sections[0]
+            .entries
+            .iter()
+            .map(|entry| entry.link.clone())
+            .collect();
+        assert!(!links.contains(&"index.html".to_string()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter() -> Result<(), SousError> {
+        let path = gen_cookbook("filter", None);
+        let cookbook = Cookbook::open(&path)?;
+
+        let filter = Filter {
+            tags: vec!["y".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(cookbook.filter(&filter)?, vec!["a.yml", "b.yml"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search() -> Result<(), SousError> {
+        let path = gen_cookbook("search", None);
+        let cookbook = Cookbook::open(&path)?;
+
+        let results = cookbook.search(&Query::parse("tag:y")?)?;
+        let files: Vec<&str> = results.iter().map(|r| r.file.as_str()).collect();
+        assert_eq!(files, vec!["a.yml", "b.yml"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_summaries_cached() -> Result<(), SousError> {
+        let path = gen_cookbook("cached", None);
+        let cookbook = Cookbook::open(&path)?.with_cache();
+
+        let uncached = Cookbook::open(&path)?.summaries()?;
+        assert_eq!(cookbook.summaries()?, uncached);
+        assert!(path.join(CACHE_FILE).is_file());
+        assert_eq!(cookbook.summaries()?, uncached);
+
+        let reopened = Cookbook::open(&path)?;
+        assert_eq!(reopened.recipes(), &vec!["a.yml", "b.yml", "c.yml"]);
+        assert_eq!(reopened.summaries()?, uncached);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_missing_recipe() {
+        let manifest = "
+chapters:
+  - title: first
+    recipes: [d]
+        ";
+        let path = gen_cookbook("missing", Some(manifest));
+
+        assert!(matches!(
+            Cookbook::open(&path),
+            Err(SousError::MissingRecipe(name)) if name == "d"
+        ));
     }
 }
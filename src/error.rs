@@ -9,6 +9,18 @@ pub enum SousError {
     #[error(transparent)]
     YamlError(#[from] serde_yaml::Error),
 
+    /// An error that occurs when parsing or writing JSON.
+    #[error(transparent)]
+    JsonError(#[from] serde_json::Error),
+
+    /// An error that occurs when writing TOML.
+    #[error(transparent)]
+    TomlError(#[from] toml::ser::Error),
+
+    /// An error that occurs when parsing TOML.
+    #[error(transparent)]
+    TomlParseError(#[from] toml::de::Error),
+
     /// An error involving file I/O; wraps [std::io::Error].
     #[error(transparent)]
     FileError(#[from] std::io::Error),
@@ -17,6 +29,70 @@ pub enum SousError {
     #[error(transparent)]
     TemplateError(#[from] tera::Error),
 
+    /// A recipe requested from or listed in a cookbook could not be found.
+    #[error("recipe not found in cookbook: {0}")]
+    MissingRecipe(String),
+
+    /// An unrecognized unit system name.
+    #[error("unknown unit system: {0}")]
+    UnknownUnitSystem(String),
+
+    /// An unrecognized built-in theme name.
+    #[error("unknown theme: {0}")]
+    UnknownTheme(String),
+
+    /// An unrecognized page size name.
+    #[error("unknown page size: {0}")]
+    UnknownPageSize(String),
+
+    /// An unrecognized Markdown ingredient list style.
+    #[error("unknown ingredient style: {0}")]
+    UnknownIngredientStyle(String),
+
+    /// An unrecognized locale.
+    #[error("unknown locale: {0}")]
+    UnknownLocale(String),
+
+    /// An unrecognized front matter style.
+    #[error("unknown front matter style: {0}")]
+    UnknownFrontMatterStyle(String),
+
+    /// An unrecognized front matter format.
+    #[error("unknown front matter format: {0}")]
+    UnknownFrontMatterFormat(String),
+
+    /// An unrecognized recipe file format.
+    #[error("unknown recipe format: {0}")]
+    UnknownRecipeFormat(String),
+
+    /// A recipe written for a newer or invalid version of the recipe format.
+    #[error("unsupported schema version: {0}")]
+    UnsupportedSchemaVersion(String),
+
+    /// A renderer producing binary output was asked for a String.
+    #[error("renderer produces binary output")]
+    BinaryOutput,
+
+    /// One or more problems found while validating a recipe.
+    #[error("validation failed: {}", .0.join("; "))]
+    ValidationError(Vec<String>),
+
+    /// An ingredient line without an ingredient name.
+    #[error("invalid ingredient: {0}")]
+    InvalidIngredient(String),
+
+    /// A document that could not be imported as a recipe.
+    #[error("failed to import recipe: {0}")]
+    ImportError(String),
+
+    /// A recipe in a cookbook that could not be loaded, with the name of its file.
+    #[error("invalid recipe {0}: {1}")]
+    RecipeError(String, Box<SousError>),
+
+    /// A malformed search query.
+    #[error("invalid query: {0}")]
+    QueryError(String),
+
     /// An unknown internal error, likely indicates a bug.
     #[error("Unknown internal error")]
     Unknown,
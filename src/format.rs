@@ -0,0 +1,163 @@
+//! Helpers for formatting recipe information as text.
+
+/// Convert text into a lowercase, URL-safe identifier (e.g. "Gluten Free!" becomes
+/// "gluten-free").
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Fractions recognized when formatting amounts, with their decimal values.
+const FRACTIONS: [(f32, &str); 9] = [
+    (0.125, "1/8"),
+    (0.25, "1/4"),
+    (1.0 / 3.0, "1/3"),
+    (0.375, "3/8"),
+    (0.5, "1/2"),
+    (0.625, "5/8"),
+    (2.0 / 3.0, "2/3"),
+    (0.75, "3/4"),
+    (0.875, "7/8"),
+];
+
+/// Format an amount as a whole number and common fraction where possible (e.g. 1.5 becomes
+/// "1 1/2"), falling back to at most two decimal places. Amounts smaller than the smallest
+/// fraction are written exactly (e.g. "0.004").
+pub fn fraction(amount: f32) -> String {
+    if amount < 0.0 {
+        return format!("-{}", fraction(-amount));
+    }
+    if amount > 0.0 && amount < FRACTIONS[0].0 - 0.01 {
+        return amount.to_string();
+    }
+
+    let whole = amount.trunc();
+    let rest = amount - whole;
+
+    if rest < 0.01 {
+        return format!("{whole}");
+    }
+    if rest > 0.99 {
+        return format!("{}", whole + 1.0);
+    }
+
+    match FRACTIONS
+        .iter()
+        .find(|(value, _)| (rest - value).abs() < 0.01)
+    {
+        Some((_, fraction)) if whole == 0.0 => fraction.to_string(),
+        Some((_, fraction)) => format!("{whole} {fraction}"),
+        None => format!("{}", (amount * 100.0).round() / 100.0),
+    }
+}
+
+/// Vulgar fraction characters recognized when parsing amounts, with their decimal values.
+const VULGAR_FRACTIONS: [(char, f32); 9] = [
+    ('⅛', 0.125),
+    ('¼', 0.25),
+    ('⅓', 1.0 / 3.0),
+    ('⅜', 0.375),
+    ('½', 0.5),
+    ('⅝', 0.625),
+    ('⅔', 2.0 / 3.0),
+    ('¾', 0.75),
+    ('⅞', 0.875),
+];
+
+/// Parse an amount written as a number, a fraction or both, as formatted by [fraction] (e.g.
+/// "1 1/2", "1.5", "1,5" or "1½").
+///
+/// Returns [None] if the text is not an amount.
+pub(crate) fn parse_amount(text: &str) -> Option<f32> {
+    let text = text.trim();
+    if let Some((whole, rest)) = text.split_once(char::is_whitespace) {
+        return Some(parse_amount(whole)? + parse_amount(rest)?);
+    }
+
+    let mut chars = text.chars();
+    if let Some(value) = chars.next_back().and_then(|last| {
+        VULGAR_FRACTIONS
+            .iter()
+            .find(|(c, _)| *c == last)
+            .map(|(_, value)| *value)
+    }) {
+        return match chars.as_str() {
+            "" => Some(value),
+            whole => Some(parse_amount(whole)? + value),
+        };
+    }
+
+    let amount = match text.split_once('/') {
+        Some((numerator, denominator)) => {
+            numerator.parse::<u32>().ok()? as f32 / denominator.parse::<u32>().ok()? as f32
+        }
+        None if text.starts_with(|c: char| c.is_ascii_digit()) => {
+            text.replace(',', ".").parse().ok()?
+        }
+        None => return None,
+    };
+
+    amount.is_finite().then_some(amount)
+}
+
+/// Format a number of minutes as hours and minutes (e.g. 75 becomes "1 h 15 min").
+pub fn duration(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes} min"),
+        (hours, 0) => format!("{hours} h"),
+        (hours, minutes) => format!("{hours} h {minutes} min"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Gluten Free!"), "gluten-free");
+        assert_eq!(slugify("  Crème  Brûlée "), "crème-brûlée");
+        assert_eq!(slugify("--"), "");
+    }
+
+    #[test]
+    fn test_fraction() {
+        assert_eq!(fraction(2.0), "2");
+        assert_eq!(fraction(1.5), "1 1/2");
+        assert_eq!(fraction(1.0 / 3.0), "1/3");
+        assert_eq!(fraction(0.999), "1");
+        assert_eq!(fraction(1.81), "1.81");
+        assert_eq!(fraction(237.0), "237");
+        assert_eq!(fraction(-1.5), "-1 1/2");
+        assert_eq!(fraction(-0.25), "-1/4");
+        assert_eq!(fraction(0.004), "0.004");
+        assert_eq!(fraction(0.0), "0");
+    }
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(parse_amount("2"), Some(2.0));
+        assert_eq!(parse_amount("1 1/2"), Some(1.5));
+        assert_eq!(parse_amount("0,25"), Some(0.25));
+        assert_eq!(parse_amount("1½"), Some(1.5));
+        assert_eq!(parse_amount("¾"), Some(0.75));
+        assert_eq!(parse_amount("1/0"), None);
+        assert_eq!(parse_amount("cup"), None);
+        assert_eq!(parse_amount("2 cups"), None);
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(duration(45), "45 min");
+        assert_eq!(duration(60), "1 h");
+        assert_eq!(duration(75), "1 h 15 min");
+    }
+}
@@ -0,0 +1,434 @@
+//! Import of recipes written as Markdown, in roughly the layout of
+//! [MarkdownRenderer](crate::MarkdownRenderer).
+
+use serde_yaml::{Mapping, Value};
+
+use crate::format::parse_amount;
+use crate::units::known_unit;
+use crate::{Ingredient, Locale, Metadata, Recipe, RecipeFormat, SousError, Text};
+
+/// Locales whose labels are recognized in section headings and meta lines.
+const LOCALES: [Locale; 3] = [Locale::English, Locale::German, Locale::French];
+
+/// Headings commonly used for the method of a recipe, besides the labels of every locale.
+const METHOD_HEADINGS: [&str; 5] = [
+    "directions",
+    "instructions",
+    "preparation",
+    "procedure",
+    "steps",
+];
+
+/// Section of a Markdown recipe being read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Section {
+    /// Title, author and meta lines before the first section heading.
+    Head,
+    /// List or table of ingredients.
+    Ingredients,
+    /// Steps of the method.
+    Method,
+    /// Any other section, which is skipped.
+    Other,
+}
+
+/// Build a [Recipe] from Markdown, see [Recipe::from_markdown].
+pub(crate) fn markdown(content: &str) -> Result<Recipe, SousError> {
+    let (mut fields, body) = front_matter(content)?;
+    let mut ingredients: Vec<Ingredient> = Vec::new();
+    let mut steps: Vec<String> = Vec::new();
+
+    let mut title = fields.contains_key("name");
+    let mut section = Section::Head;
+    let mut section_level = 0;
+    let mut table_header = true;
+    let mut paragraph = true;
+
+    let offset = content[..content.len() - body.len()].matches('\n').count();
+    for (number, line) in body.lines().enumerate() {
+        let invalid =
+            |e: SousError| SousError::ImportError(format!("line {}: {e}", offset + number + 1));
+        let trimmed = line.trim();
+        if trimmed.is_empty() || is_rule(trimmed) {
+            paragraph = true;
+            continue;
+        }
+
+        if let Some((level, heading)) = heading(trimmed) {
+            if !title && (level == 1 || section_kind(heading).is_none()) {
+                fields.insert(Value::from("name"), Value::from(heading));
+                title = true;
+            } else if section == Section::Head || level <= section_level {
+                section = section_kind(heading).unwrap_or(Section::Other);
+                section_level = level;
+                table_header = true;
+                paragraph = true;
+            }
+            continue;
+        }
+
+        match section {
+            Section::Head => head_line(&mut fields, trimmed),
+            Section::Ingredients => {
+                if let Some(row) = trimmed.strip_prefix('|') {
+                    if !std::mem::take(&mut table_header) {
+                        if let Some(ingredient) = table_row(row).map_err(invalid)? {
+                            ingredients.push(ingredient);
+                        }
+                    }
+                } else if let Some(item) = list_item(trimmed) {
+                    ingredients.push(item.parse().map_err(invalid)?);
+                }
+            }
+            Section::Method => {
+                let continues = !paragraph || line.starts_with(char::is_whitespace);
+                match (list_item(trimmed), steps.last_mut()) {
+                    (Some(item), _) => steps.push(item.to_string()),
+                    (None, Some(step)) if continues => {
+                        step.push(' ');
+                        step.push_str(trimmed);
+                    }
+                    (None, _) => steps.push(trimmed.to_string()),
+                }
+                paragraph = false;
+            }
+            Section::Other => {}
+        }
+    }
+
+    if !title {
+        return Err(SousError::ImportError("missing title".to_string()));
+    }
+    for (key, default) in [
+        ("author", Value::from("")),
+        ("servings", Value::from(1)),
+        ("cook_minutes", Value::from(0)),
+    ] {
+        if !fields.contains_key(key) {
+            fields.insert(Value::from(key), default);
+        }
+    }
+    let metadata: Metadata = serde_yaml::from_value(Value::Mapping(fields))?;
+
+    Ok(Recipe {
+        metadata,
+        ingredients,
+        steps: steps.into_iter().map(Text::from).collect(),
+        ..Default::default()
+    })
+}
+
+/// Split YAML or TOML front matter from the body, as written by [MarkdownRenderer::front_matter]
+/// in the default [FrontMatterStyle](crate::FrontMatterStyle).
+///
+/// The front matter `title` becomes the recipe's name, and the calculated `total_minutes` is left
+/// out.
+///
+/// [MarkdownRenderer::front_matter]: crate::MarkdownRenderer::front_matter
+fn front_matter(content: &str) -> Result<(Mapping, &str), SousError> {
+    let content = content.trim_start();
+    let (delimiter, format) = match content.lines().next() {
+        Some("---") => ("---", RecipeFormat::Yaml),
+        Some("+++") => ("+++", RecipeFormat::Toml),
+        _ => return Ok((Mapping::new(), content)),
+    };
+
+    let rest = &content[delimiter.len()..];
+    let end = rest
+        .match_indices(&format!("\n{delimiter}"))
+        .map(|(i, _)| i)
+        .find(|i| {
+            let after = &rest[i + 1 + delimiter.len()..];
+            after.is_empty() || after.starts_with(['\n', '\r'])
+        })
+        .ok_or_else(|| SousError::ImportError("unterminated front matter".to_string()))?;
+
+    let mut fields = Mapping::new();
+    if let Value::Mapping(mapping) = format.parse(&rest[..end])? {
+        for (key, value) in mapping {
+            match key.as_str() {
+                Some("title") => fields.insert(Value::from("name"), value),
+                Some("total_minutes") => None,
+                _ => fields.insert(key, value),
+            };
+        }
+    }
+
+    Ok((fields, &rest[end + 1 + delimiter.len()..]))
+}
+
+/// Parse an ATX heading into its level and text.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+
+    (1..=6)
+        .contains(&level)
+        .then(|| (level, text.trim_end_matches('#').trim()))
+}
+
+/// Whether a line is a thematic break, such as `---` or `***`.
+fn is_rule(line: &str) -> bool {
+    let marks = line
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+    marks.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|mark| marks.chars().all(|c| c == *mark))
+}
+
+/// Get the section introduced by a heading, if it is an ingredient or method heading.
+fn section_kind(heading: &str) -> Option<Section> {
+    let heading = heading.trim_end_matches(':').trim().to_lowercase();
+    let labels = LOCALES.map(|locale| locale.labels());
+
+    if labels
+        .iter()
+        .any(|labels| labels.ingredients.to_lowercase() == heading)
+    {
+        Some(Section::Ingredients)
+    } else if labels
+        .iter()
+        .any(|labels| labels.method.to_lowercase() == heading)
+        || METHOD_HEADINGS.contains(&heading.as_str())
+    {
+        Some(Section::Method)
+    } else {
+        None
+    }
+}
+
+/// Get the text of a bulleted, task or numbered list item.
+fn list_item(line: &str) -> Option<&str> {
+    let item = match line.strip_prefix(['*', '-', '+']) {
+        Some(item) => item,
+        None => {
+            let number = line.chars().take_while(char::is_ascii_digit).count();
+            line[number..]
+                .strip_prefix(['.', ')'])
+                .filter(|_| number > 0)?
+        }
+    };
+    let item = item.strip_prefix(' ')?.trim_start();
+
+    Some(
+        ["[ ] ", "[x] ", "[X] "]
+            .iter()
+            .find_map(|task| item.strip_prefix(task))
+            .unwrap_or(item)
+            .trim(),
+    )
+}
+
+/// Read a line before the first section: bold author and meta lines, and extra fields listed as
+/// `* key: value`.
+fn head_line(fields: &mut Mapping, line: &str) {
+    if let Some(bold) = line
+        .strip_prefix("**")
+        .and_then(|line| line.strip_suffix("**"))
+    {
+        let segments: Vec<&str> = bold.split(" | ").map(str::trim).collect();
+        let meta: Vec<(&str, u32)> = segments.iter().filter_map(|s| meta_segment(s)).collect();
+
+        if meta.is_empty() {
+            fields.insert(Value::from("author"), Value::from(segments[0]));
+            if let Some(url) = segments.get(1) {
+                fields.insert(Value::from("url"), Value::from(*url));
+            }
+        }
+        for (key, value) in meta {
+            fields.insert(Value::from(key), Value::from(value));
+        }
+    } else if let Some((key, value)) = list_item(line).and_then(|item| item.split_once(": ")) {
+        let value = serde_yaml::from_str(value).unwrap_or_else(|_| Value::from(value));
+        fields.entry(Value::from(key.trim())).or_insert(value);
+    }
+}
+
+/// Parse a segment of a meta line, such as "4 servings" or "10 minutes prep", into the [Metadata]
+/// field it sets.
+fn meta_segment(segment: &str) -> Option<(&'static str, u32)> {
+    let (number, label) = segment.split_once(' ')?;
+    let number = number.parse().ok()?;
+    let label = label.trim().to_lowercase();
+
+    for labels in LOCALES.map(|locale| locale.labels()) {
+        for (field, text) in [
+            ("servings", &labels.servings),
+            ("prep_minutes", &labels.prep),
+            ("cook_minutes", &labels.cook),
+        ] {
+            if text.to_lowercase() == label {
+                return Some((field, number));
+            }
+        }
+    }
+
+    if label.contains("serv") {
+        Some(("servings", number))
+    } else if label.contains("prep") {
+        Some(("prep_minutes", number))
+    } else if label.contains("cook") {
+        Some(("cook_minutes", number))
+    } else {
+        None
+    }
+}
+
+/// Read a row of an ingredient table with amount, unit and name columns, skipping the separator
+/// row. Tables with other columns are read as ingredient lines.
+fn table_row(row: &str) -> Result<Option<Ingredient>, SousError> {
+    let row = row
+        .trim_end()
+        .strip_suffix('|')
+        .unwrap_or(row)
+        .replace("\\|", "\u{0}");
+    let cells: Vec<String> = row
+        .split('|')
+        .map(|cell| cell.trim().replace('\u{0}', "|"))
+        .collect();
+
+    if cells
+        .iter()
+        .all(|cell| !cell.is_empty() && cell.chars().all(|c| matches!(c, '-' | ':')))
+    {
+        return Ok(None);
+    }
+
+    match cells.as_slice() {
+        [amount, unit, name] if !name.is_empty() => Ok(Some(Ingredient {
+            name: name.as_str().into(),
+            amount: parse_amount(amount),
+            unit: match unit.as_str() {
+                "" => None,
+                unit => Some(known_unit(unit).unwrap_or(unit).to_string()),
+            },
+            ..Default::default()
+        })),
+        _ => cells.join(" ").parse().map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IngredientStyle, MarkdownRenderer, Renderer};
+
+    fn gen_recipe() -> Recipe {
+        Recipe {
+            metadata: Metadata {
+                name: "Pancakes".into(),
+                author: "test author".to_string(),
+                url: Some("https://example.com/pancakes".to_string()),
+                servings: 4,
+                prep_minutes: Some(5),
+                cook_minutes: 10,
+                ..Default::default()
+            },
+            ingredients: vec![
+                "1 1/2 cups flour".parse().unwrap(),
+                "2 eggs".parse().unwrap(),
+                "1 pinch salt".parse().unwrap(),
+            ],
+            steps: vec!["Whisk everything.".into(), "Fry.".into()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_markdown_round_trip() -> Result<(), SousError> {
+        let recipe = gen_recipe();
+
+        for style in [IngredientStyle::Dash, IngredientStyle::Table] {
+            let renderer = MarkdownRenderer {
+                ingredient_style: style,
+                ..Default::default()
+            };
+            assert_eq!(markdown(&renderer.render(&recipe)?)?, recipe, "{style}");
+        }
+
+        let renderer = MarkdownRenderer {
+            front_matter: true,
+            ..Default::default()
+        };
+        assert_eq!(markdown(&renderer.render(&recipe)?)?, recipe);
+
+        let renderer = MarkdownRenderer {
+            labels: Locale::German.labels(),
+            ..Default::default()
+        };
+        assert_eq!(markdown(&renderer.render(&recipe)?)?, recipe);
+        Ok(())
+    }
+
+    #[test]
+    fn test_markdown_legacy() -> Result<(), SousError> {
+        let md = "
+# Tomato Soup #
+
+**Grandma**
+
+Serves a crowd.
+
+* rating: 5
+
+## Ingredients:
+
+### Soup
+
+- [ ] 2 cans tomatoes
+- [x] 1 onion
+
+## Directions
+
+1. Chop the onion
+   finely.
+2. Simmer.
+
+Blend until smooth.
+
+## Notes
+
+- Freezes well.
+";
+        let recipe = markdown(md)?;
+
+        assert_eq!(recipe.metadata.name, "Tomato Soup");
+        assert_eq!(recipe.metadata.author, "Grandma");
+        assert_eq!(recipe.metadata.servings, 1);
+        assert_eq!(recipe.metadata.extra["rating"], Value::from(5));
+        assert_eq!(recipe.ingredients.len(), 2);
+        assert_eq!(recipe.ingredients[0].unit.as_deref(), Some("cans"));
+        assert_eq!(
+            recipe.steps,
+            vec![
+                Text::from("Chop the onion finely."),
+                Text::from("Simmer."),
+                Text::from("Blend until smooth."),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_markdown_invalid_ingredient() {
+        let md = "---\nname: Soup\n---\n## Ingredients\n\n- 1 onion\n- 2 cups\n";
+
+        match markdown(md) {
+            Err(SousError::ImportError(message)) => {
+                assert_eq!(message, "line 7: invalid ingredient: 2 cups")
+            }
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_markdown_missing_title() {
+        assert!(matches!(
+            markdown("## Ingredients\n\n- 1 egg\n"),
+            Err(SousError::ImportError(_))
+        ));
+    }
+}
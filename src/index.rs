@@ -0,0 +1,73 @@
+//! Types for cookbook indexes and tables of contents.
+
+use serde::Serialize;
+
+use crate::Recipe;
+
+/// Table of contents for a cookbook, grouped into sections.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize)]
+pub struct Index {
+    /// Optional title of the cookbook.
+    pub title: Option<String>,
+    /// Optional author or editor of the cookbook.
+    pub author: Option<String>,
+    /// Optional description of the cookbook.
+    pub description: Option<String>,
+    /// Ordered list of [IndexSection]s.
+    pub sections: Vec<IndexSection>,
+}
+
+/// A titled group of entries within an [Index].
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize)]
+pub struct IndexSection {
+    /// Display title of the section.
+    pub title: String,
+    /// Entries in the section, in order.
+    pub entries: Vec<IndexEntry>,
+}
+
+/// Summary of a single recipe within an [Index].
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize)]
+pub struct IndexEntry {
+    /// Display name of the recipe.
+    pub name: String,
+    /// Original author of the recipe.
+    pub author: String,
+    /// Relative link to the rendered recipe.
+    pub link: String,
+    /// File name of the recipe within its cookbook, if known.
+    pub file: String,
+    /// Servings yielded by the recipe as written.
+    pub servings: u32,
+    /// Total estimated time in minutes, including prep.
+    pub total_minutes: u32,
+    /// Tags describing the recipe.
+    pub tags: Vec<String>,
+}
+
+impl Index {
+    /// Create a new, empty index.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl IndexEntry {
+    /// Create an entry for the provided [Recipe], linking to `link`.
+    ///
+    /// The name is [localized](Recipe::localized) into `language`, defaulting to the recipe's own
+    /// language.
+    pub fn new(recipe: &Recipe, link: String, language: Option<&str>) -> Self {
+        let recipe = recipe.localized(language.unwrap_or(recipe.default_language()));
+
+        IndexEntry {
+            name: recipe.metadata.name.to_string(),
+            author: recipe.metadata.author.clone(),
+            link,
+            file: String::new(),
+            servings: recipe.metadata.servings,
+            total_minutes: recipe.metadata.total_minutes(),
+            tags: recipe.metadata.tags.clone(),
+        }
+    }
+}
@@ -1,19 +1,37 @@
 //! Types for representing ingredients.
 
 use std::fmt;
-use std::fmt::Write;
+use std::str::FromStr;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::format::parse_amount;
+use crate::translation::Text;
+use crate::units::{known_unit, UnitSystem};
+use crate::SousError;
+
+/// Units that cannot be converted but are recognized when [parsing](Ingredient::from_str) an
+/// ingredient line, kept as written.
+const COUNT_UNITS: [&str; 24] = [
+    "bunch", "bunches", "can", "cans", "clove", "cloves", "dash", "dashes", "handful", "handfuls",
+    "package", "packages", "piece", "pieces", "pinch", "pinches", "slice", "slices", "sprig",
+    "sprigs", "stalk", "stalks", "stick", "sticks",
+];
+
 /// An ingredient used in a culinary recipe.
-#[derive(Clone, Debug, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, PartialOrd, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Ingredient {
     /// The ingredient's display name.
-    pub name: String,
+    pub name: Text,
     /// Optional amount of the ingredient to be used.
     pub amount: Option<f32>,
     /// Optional unit description.
     pub unit: Option<String>,
+    /// Optional file name of another recipe in the same cookbook that prepares this ingredient.
+    ///
+    /// The extension may be omitted (e.g. `pie-crust` for `pie-crust.yml`).
+    pub recipe: Option<String>,
 }
 
 impl Ingredient {
@@ -22,25 +40,180 @@ impl Ingredient {
         Default::default()
     }
 
-    /// Generate a human-readable [String] representation of the ingredient.
-    pub fn to_string(&self) -> String {
-        let mut ret = String::new();
+    /// Convert the ingredient's amount and unit into the given [UnitSystem].
+    ///
+    /// Ingredients without an amount or with an unrecognized unit are returned unchanged.
+    pub fn to_system(&self, system: UnitSystem) -> Ingredient {
+        if let (Some(amount), Some(unit)) = (self.amount, &self.unit) {
+            if let Some((amount, unit)) = system.convert(amount, unit) {
+                return Ingredient {
+                    amount: Some(amount),
+                    unit: Some(unit.to_string()),
+                    ..self.clone()
+                };
+            }
+        }
 
+        self.clone()
+    }
+}
+
+/// Formats the ingredient as a line of a recipe, e.g. "1.5 cup flour".
+///
+/// Use [Locale::ingredient](crate::Locale::ingredient) for common fractions and pluralized units.
+impl fmt::Display for Ingredient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(amount) = &self.amount {
-            write!(ret, "{} ", amount).unwrap();
+            write!(f, "{} ", amount)?;
         }
 
         if let Some(unit) = &self.unit {
-            write!(ret, "{} ", unit).unwrap();
+            write!(f, "{} ", unit)?;
         }
 
-        write!(ret, "{}", self.name).unwrap();
-        ret
+        write!(f, "{}", self.name)
     }
 }
 
-impl fmt::Display for Ingredient {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+/// Parses a line of a recipe, e.g. "1 1/2 cups flour", including lines written by the
+/// [Display](fmt::Display) implementation or [Locale::ingredient](crate::Locale::ingredient).
+///
+/// A leading amount may be a number, a fraction or both, and is followed by an optional unit.
+/// Recognized units are stored by their [canonical names](crate::normalize_unit), so "cups" becomes
+/// "cup", and a unit may also be attached to the amount (e.g. "200g"). Returns
+/// [SousError::InvalidIngredient] if no name remains.
+impl FromStr for Ingredient {
+    type Err = SousError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ingredient = Ingredient::new();
+        let mut rest = s.trim();
+
+        let words: Vec<&str> = rest.split_whitespace().take(2).collect();
+        for count in (1..=words.len()).rev() {
+            if let Some(amount) = parse_amount(&words[..count].join(" ")) {
+                ingredient.amount = Some(amount);
+                rest = skip_words(rest, count);
+                break;
+            }
+        }
+
+        if ingredient.amount.is_none() {
+            let word = words.first().copied().unwrap_or_default();
+            if let Some(i) = word.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ',')) {
+                let (amount, unit) = word.split_at(i);
+                if let (Some(amount), Some(unit)) = (parse_amount(amount), known_unit(unit)) {
+                    ingredient.amount = Some(amount);
+                    ingredient.unit = Some(unit.to_string());
+                    rest = skip_words(rest, 1);
+                }
+            }
+        } else {
+            let words: Vec<&str> = rest.split_whitespace().take(2).collect();
+            for count in (1..=words.len()).rev() {
+                let unit = words[..count].join(" ");
+                let unit = unit.trim_end_matches('.');
+                if let Some(known) = known_unit(unit) {
+                    ingredient.unit = Some(known.to_string());
+                } else if count == 1 && COUNT_UNITS.contains(&unit.to_lowercase().as_str()) {
+                    ingredient.unit = Some(unit.to_string());
+                } else {
+                    continue;
+                }
+                rest = skip_words(rest, count);
+                break;
+            }
+        }
+
+        if ingredient.unit.is_some() {
+            rest = rest.strip_prefix("of ").unwrap_or(rest).trim_start();
+        }
+        if rest.is_empty() {
+            return Err(SousError::InvalidIngredient(s.trim().to_string()));
+        }
+        ingredient.name = rest.into();
+
+        Ok(ingredient)
+    }
+}
+
+/// Remove the first `count` whitespace-separated words from `text`.
+fn skip_words(text: &str, count: usize) -> &str {
+    let mut rest = text.trim_start();
+    for _ in 0..count {
+        rest = rest
+            .trim_start_matches(|c: char| !c.is_whitespace())
+            .trim_start();
+    }
+    rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let ingredient = Ingredient {
+            name: "flour".into(),
+            amount: Some(1.5),
+            unit: Some("cup".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(ingredient.to_string(), "1.5 cup flour");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let parse = |line: &str| {
+            let ingredient: Ingredient = line.parse().unwrap();
+            (
+                ingredient.amount,
+                ingredient.unit,
+                ingredient.name.to_string(),
+            )
+        };
+
+        assert_eq!(
+            parse("1 1/2 cups flour"),
+            (Some(1.5), Some("cup".to_string()), "flour".to_string())
+        );
+        assert_eq!(
+            parse("2 fl oz of cream"),
+            (Some(2.0), Some("fl oz".to_string()), "cream".to_string())
+        );
+        assert_eq!(
+            parse("200g butter"),
+            (Some(200.0), Some("g".to_string()), "butter".to_string())
+        );
+        assert_eq!(
+            parse("3 Cloves garlic"),
+            (Some(3.0), Some("Cloves".to_string()), "garlic".to_string())
+        );
+        assert_eq!(parse("2 eggs"), (Some(2.0), None, "eggs".to_string()));
+        assert_eq!(
+            parse("salt to taste"),
+            (None, None, "salt to taste".to_string())
+        );
+        assert!(matches!(
+            "2 cups".parse::<Ingredient>(),
+            Err(SousError::InvalidIngredient(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_str_display() {
+        let ingredient = Ingredient {
+            name: "sugar".into(),
+            amount: Some(0.75),
+            unit: Some("tbsp".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ingredient.to_string().parse::<Ingredient>().unwrap(),
+            ingredient
+        );
     }
 }
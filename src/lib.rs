@@ -11,18 +11,18 @@
 //! fn main() {
 //!     let mut recipe = Recipe::new();
 //!
-//!     recipe.metadata.name = "Test Recipe".to_string();
+//!     recipe.metadata.name = "Test Recipe".into();
 //!     recipe.metadata.author = "Cook Cookerson".to_string();
 //!     recipe.metadata.servings = 2;
 //!     recipe.metadata.cook_minutes = 10;
 //!
 //!     recipe.ingredients.push(Ingredient {
-//!         name: "Ingredient".to_string(),
+//!         name: "Ingredient".into(),
 //!         amount: Some(1.0),
 //!         ..Default::default()
 //!     });
-//!     recipe.steps.push("First step".to_string());
-//!     recipe.steps.push("Second step".to_string());
+//!     recipe.steps.push("First step".into());
+//!     recipe.steps.push("Second step".into());
 //!
 //!     let render = MarkdownRenderer::new();
 //!     let md = render.render(&recipe);
@@ -31,18 +31,110 @@
 
 #![warn(missing_docs)]
 
+mod cache;
 mod cookbook;
 mod error;
+mod format;
+mod import;
+mod index;
 mod ingredient;
+mod locale;
+mod manifest;
 mod metadata;
+mod migration;
 mod recipe;
 mod render;
+mod search;
+mod site;
+mod translation;
+mod units;
 
 pub use crate::cookbook::Cookbook;
 pub use crate::error::SousError;
+pub use crate::format::duration;
+pub use crate::format::fraction;
+pub use crate::format::slugify;
+pub use crate::index::Index;
+pub use crate::index::IndexEntry;
+pub use crate::index::IndexSection;
 pub use crate::ingredient::Ingredient;
+pub use crate::locale::Locale;
+pub use crate::manifest::Chapter;
+pub use crate::manifest::Defaults;
+pub use crate::manifest::Manifest;
+pub use crate::metadata::Filter;
 pub use crate::metadata::Metadata;
+pub use crate::metadata::Vocabulary;
+pub use crate::migration::migrate;
+pub use crate::migration::schema_version;
+pub use crate::migration::SCHEMA_VERSION;
 pub use crate::recipe::Recipe;
+pub use crate::recipe::RecipeFormat;
+pub use crate::render::front_matter;
+pub use crate::render::AsciiDocRenderer;
+pub use crate::render::FrontMatterFormat;
+pub use crate::render::FrontMatterStyle;
+pub use crate::render::HtmlRenderer;
+pub use crate::render::IngredientStyle;
+pub use crate::render::Labels;
+pub use crate::render::LatexRenderer;
 pub use crate::render::MarkdownRenderer;
+pub use crate::render::PageSize;
+pub use crate::render::PdfRenderer;
 pub use crate::render::Renderer;
+pub use crate::render::RstRenderer;
 pub use crate::render::TemplateRenderer;
+pub use crate::render::TextRenderer;
+pub use crate::search::Comparison;
+pub use crate::search::Query;
+pub use crate::search::SearchResult;
+pub use crate::search::Summary;
+pub use crate::search::TimeField;
+pub use crate::search::TimeLimit;
+pub use crate::site::Site;
+pub use crate::translation::Text;
+pub use crate::translation::DEFAULT_LANGUAGE;
+pub use crate::units::convert_unit;
+pub use crate::units::normalize_unit;
+pub use crate::units::pluralize_unit;
+pub use crate::units::UnitSystem;
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::fs;
+    use std::ops::Deref;
+    use std::path::{Path, PathBuf};
+
+    /// Temporary directory of a test, removed with its contents when dropped.
+    pub struct TempDir(PathBuf);
+
+    impl Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl AsRef<Path> for TempDir {
+        fn as_ref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// Create an empty temporary directory for the test `name`, replacing any left over by an
+    /// earlier run.
+    pub fn temp_dir(name: &str) -> TempDir {
+        let path = std::env::temp_dir().join(format!("sous-test-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        TempDir(path)
+    }
+}
@@ -0,0 +1,226 @@
+//! Types for localizing rendered recipes.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::format::fraction;
+use crate::units::pluralize_unit;
+use crate::{Ingredient, Labels, SousError};
+
+/// A language that rendered labels and numbers can be localized into.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum Locale {
+    /// English, using "." as the decimal separator.
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    /// German, using "," as the decimal separator.
+    #[serde(rename = "de")]
+    German,
+    /// French, using "," as the decimal separator.
+    #[serde(rename = "fr")]
+    French,
+}
+
+impl Locale {
+    /// Get the catalog of labels in this locale's language.
+    pub fn labels(&self) -> Labels {
+        match self {
+            Locale::English => Labels::default(),
+            Locale::German => catalog([
+                "Zutaten",
+                "Zubereitung",
+                "Portionen",
+                "Minuten Vorbereitung",
+                "Minuten Kochzeit",
+                "Minuten",
+                "Menge",
+                "Einheit",
+                "Zutat",
+                "von",
+                "Verzeichnis",
+                "Inhalt",
+                "Rezepte",
+                "Weitere Rezepte",
+                "Ohne Schlagwort",
+            ]),
+            Locale::French => catalog([
+                "Ingrédients",
+                "Préparation",
+                "portions",
+                "minutes de préparation",
+                "minutes de cuisson",
+                "minutes",
+                "Quantité",
+                "Unité",
+                "Ingrédient",
+                "par",
+                "Index",
+                "Sommaire",
+                "Recettes",
+                "Autres recettes",
+                "Sans étiquette",
+            ]),
+        }
+    }
+
+    /// Character separating the integer and fractional parts of a decimal number.
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            Locale::English => '.',
+            Locale::German | Locale::French => ',',
+        }
+    }
+
+    /// Format a number with up to two decimals, e.g. "0,15" in German.
+    pub fn number(&self, amount: f32) -> String {
+        let formatted = format!("{:.2}", amount);
+        let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+        formatted.replace('.', &self.decimal_separator().to_string())
+    }
+
+    /// Format an amount with common fractions like [fraction], using this locale's decimal
+    /// separator for amounts that have no common fraction.
+    pub fn fraction(&self, amount: f32) -> String {
+        fraction(amount).replace('.', &self.decimal_separator().to_string())
+    }
+
+    /// Pluralize a unit like [pluralize_unit]. Units are only pluralized in English, as the
+    /// recognized unit names are English words.
+    pub fn pluralize_unit(&self, unit: &str, amount: f32) -> String {
+        match self {
+            Locale::English => pluralize_unit(unit, amount),
+            Locale::German | Locale::French => unit.to_string(),
+        }
+    }
+
+    /// Format an amount exactly, using this locale's decimal separator, e.g. "0,333" in German.
+    pub fn decimal(&self, amount: f32) -> String {
+        amount
+            .to_string()
+            .replace('.', &self.decimal_separator().to_string())
+    }
+
+    /// Format an ingredient as a line of a recipe with common fractions and pluralized units, e.g.
+    /// "1 1/2 cups flour".
+    pub fn ingredient(&self, ingredient: &Ingredient) -> String {
+        let mut line = String::new();
+        if let Some(amount) = ingredient.amount {
+            line.push_str(&self.fraction(amount));
+            line.push(' ');
+        }
+        if let Some(unit) = &ingredient.unit {
+            line.push_str(&self.pluralize_unit(unit, ingredient.amount.unwrap_or(1.0)));
+            line.push(' ');
+        }
+        line.push_str(ingredient.name.as_str());
+        line
+    }
+}
+
+/// Build [Labels] from a catalog of translations, in the order the fields are declared.
+fn catalog(labels: [&str; 15]) -> Labels {
+    let [ingredients, method, servings, prep, cook, minutes, amount, unit, ingredient, by, index, contents, recipes, other_recipes, untagged] =
+        labels.map(str::to_string);
+
+    Labels {
+        ingredients,
+        method,
+        servings,
+        prep,
+        cook,
+        minutes,
+        amount,
+        unit,
+        ingredient,
+        by,
+        index,
+        contents,
+        recipes,
+        other_recipes,
+        untagged,
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Locale::English => write!(f, "en"),
+            Locale::German => write!(f, "de"),
+            Locale::French => write!(f, "fr"),
+        }
+    }
+}
+
+/// Parses a language code, ignoring any region or encoding (e.g. "de", "de-CH" or "de_DE.UTF-8").
+impl FromStr for Locale {
+    type Err = SousError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.split(['-', '_', '.']).next().unwrap_or_default();
+        match language.to_lowercase().as_str() {
+            "en" | "english" => Ok(Locale::English),
+            "de" | "german" => Ok(Locale::German),
+            "fr" | "french" => Ok(Locale::French),
+            _ => Err(SousError::UnknownLocale(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("de".parse::<Locale>().unwrap(), Locale::German);
+        assert_eq!("fr-CA".parse::<Locale>().unwrap(), Locale::French);
+        assert_eq!("en_US.UTF-8".parse::<Locale>().unwrap(), Locale::English);
+        assert!("xx".parse::<Locale>().is_err());
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(Locale::English.number(1.5), "1.5");
+        assert_eq!(Locale::German.number(1.5), "1,5");
+        assert_eq!(Locale::French.number(2.0), "2");
+    }
+
+    #[test]
+    fn test_decimal() {
+        assert_eq!(Locale::English.decimal(0.333), "0.333");
+        assert_eq!(Locale::German.decimal(1.5), "1,5");
+        assert_eq!(Locale::French.decimal(2.0), "2");
+    }
+
+    #[test]
+    fn test_fraction() {
+        assert_eq!(Locale::German.fraction(1.5), "1 1/2");
+        assert_eq!(Locale::German.fraction(0.15), "0,15");
+        assert_eq!(Locale::English.fraction(0.15), "0.15");
+    }
+
+    #[test]
+    fn test_ingredient() {
+        let ingredient = Ingredient {
+            name: "Mehl".into(),
+            amount: Some(2.0),
+            unit: Some("cup".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(Locale::German.ingredient(&ingredient), "2 cup Mehl");
+        assert_eq!(Locale::English.ingredient(&ingredient), "2 cups Mehl");
+    }
+
+    #[test]
+    fn test_labels() {
+        assert_eq!(Locale::English.labels(), Labels::default());
+        assert_eq!(Locale::German.labels().ingredients, "Zutaten");
+        assert_eq!(Locale::French.labels().cook, "minutes de cuisson");
+    }
+}
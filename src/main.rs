@@ -1,12 +1,18 @@
 use std::{
-    fs,
-    io::{stdin, Read},
-    path::PathBuf,
+    env, fs,
+    io::{stdin, stdout, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
     process,
 };
 
-use clap::{Parser, ValueEnum};
-use sous::{Cookbook, MarkdownRenderer, Recipe, Renderer, SousError, TemplateRenderer};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use sous::{
+    schema_version, slugify, AsciiDocRenderer, Cookbook, Filter, FrontMatterFormat,
+    FrontMatterStyle, HtmlRenderer, IngredientStyle, LatexRenderer, Locale, MarkdownRenderer,
+    PageSize, PdfRenderer, Query, Recipe, RecipeFormat, Renderer, RstRenderer, Site, SousError,
+    Summary, TemplateRenderer, TextRenderer, UnitSystem, DEFAULT_LANGUAGE, SCHEMA_VERSION,
+};
+use terminal_size::{terminal_size, Width};
 
 #[derive(Clone, Debug, Default, ValueEnum)]
 enum RenderMode {
@@ -15,23 +21,105 @@ enum RenderMode {
     Markdown,
     /// Use the Tera template renderer.
     Template,
+    /// Use the built-in HTML theme.
+    Html,
+    /// Use the PDF renderer.
+    Pdf,
+    /// Use the LaTeX renderer.
+    Latex,
+    /// Use the reStructuredText renderer.
+    Rst,
+    /// Use the AsciiDoc renderer.
+    Asciidoc,
+    /// Use the plain-text renderer.
+    Text,
 }
 
-/// Convert YAML culinary recipes to Markdown.
+/// Convert and manage culinary recipes written in YAML, JSON or TOML.
+///
+/// When no command is given, sous renders the given recipe or Cookbook (see `sous help render`).
 #[derive(Parser, Debug)]
-#[command()]
-struct Args {
-    /// Cookbook or single YAML-formatted recipe to convert.
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    render: Option<RenderArgs>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Convert a recipe or Cookbook to Markdown or a templated format.
+    Render(RenderArgs),
+    /// List the recipes in a Cookbook.
+    List(ListArgs),
+    /// Search the recipes in a Cookbook.
+    Search(SearchArgs),
+    /// Generate a static HTML website from a Cookbook.
+    Site(SiteArgs),
+    /// Print a recipe card or a whole Cookbook to PDF.
+    Pdf(PdfArgs),
+    /// Show a recipe from a Cookbook as plain text in the terminal.
+    Show(ShowArgs),
+    /// Rewrite recipe files in a canonical form.
+    Fmt(FmtArgs),
+    /// Convert recipe files between YAML, JSON and TOML.
+    Convert(ConvertArgs),
+    /// Import recipes written in another format, such as Markdown, as YAML.
+    Import(ImportArgs),
+    /// Upgrade recipe files written for older versions of sous to the current format.
+    Migrate(MigrateArgs),
+    /// Print a JSON Schema of the recipe format, e.g. for editor autocompletion.
+    Schema(SchemaArgs),
+}
+
+#[derive(Clone, Debug, Default, ValueEnum)]
+enum ImportFormat {
+    /// Markdown in roughly the layout written by the Markdown renderer.
+    #[default]
+    Markdown,
+}
+
+impl ImportFormat {
+    /// Extension of files imported from a directory.
+    fn extension(&self) -> &str {
+        match self {
+            ImportFormat::Markdown => "md",
+        }
+    }
+
+    fn parse(&self, content: &str) -> Result<Recipe, SousError> {
+        match self {
+            ImportFormat::Markdown => Recipe::from_markdown(content),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, ValueEnum)]
+enum OutputFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// JSON array of results.
+    Json,
+}
+
+#[derive(Args, Debug)]
+struct RenderArgs {
+    /// Cookbook or single recipe file to convert, or `-` to read YAML from standard input.
     ///
     /// Single-file mode or Cookbook mode will automatically be selected based on whether INPUT
-    /// points to a file or directory.
+    /// points to a file or directory. A YAML file of several `---` separated recipes is rendered
+    /// in Cookbook mode, naming each recipe after its title.
     #[arg()]
     input: PathBuf,
 
     /// Output path.
     ///
     /// In single-file mode, output to the specified file instead of printing to stdout. In
-    /// Cookbook mode, specify the directory in which to output (will be created if necessary).
+    /// Cookbook mode, specify the directory in which to output (will be created if necessary). A
+    /// directory is required to render several recipes read from standard input.
     #[arg(short, long)]
     output: Option<PathBuf>,
 
@@ -39,112 +127,1113 @@ struct Args {
     ///
     /// Markdown mode uses the built-in renderer to generate markdown files.
     /// Template mode uses the provided Tera template to render recipes.
-    #[arg(short, long, value_enum, default_value_t = RenderMode::Markdown)]
-    mode: RenderMode,
+    /// HTML mode renders pages with the built-in HTML theme.
+    /// PDF mode renders one PDF document per recipe.
+    /// LaTeX mode renders recipes for a typeset book, written to `index.tex` in Cookbook mode.
+    /// Rst and AsciiDoc modes render reStructuredText and AsciiDoc documents.
+    /// Text mode renders plain text, wrapped to the terminal's width and styled when printing a
+    /// single recipe to a TTY, or wrapped at 80 columns otherwise.
+    ///
+    /// Defaults to template mode for a Cookbook whose manifest sets a template, or to Markdown
+    /// mode otherwise.
+    #[arg(short, long, value_enum)]
+    mode: Option<RenderMode>,
 
-    /// File to use for the template renderer.
+    /// File or directory to use for the template renderer.
+    ///
+    /// A directory may contain `recipe`, `index` and `tag` templates (matched by file stem, e.g.
+    /// `recipe.md`) along with any layouts or partials they extend or include. When combined with
+    /// `--theme`, files in the directory replace the built-in theme's files of the same name.
     ///
-    /// If a template file is not specified, sous will read a template from standard input.
+    /// If neither a template nor a theme is specified, sous will read a template from standard
+    /// input.
     #[arg(short, long)]
     template: Option<PathBuf>,
 
-    /// Override number of servings (Only applies to the Markdown renderer).
+    /// Built-in theme to use for the template renderer, either "markdown" or "html".
+    #[arg(long)]
+    theme: Option<String>,
+
+    /// File to use for rendering the Cookbook index in template mode.
+    ///
+    /// In Cookbook mode, the Markdown renderer always generates an index. The template renderer
+    /// only generates one when an index template is provided.
+    #[arg(short, long)]
+    index_template: Option<PathBuf>,
+
+    /// Override number of servings.
     ///
     /// Render recipes with a specific number of servings. Ingredient amounts will be adjusted
-    /// accordingly.
+    /// accordingly. Templates can refer to the recipe's own serving count as
+    /// `original_servings`.
     #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
     servings: Option<u32>,
 
-    /// Use front matter instead of pure Markdown (Only applies to Markdown renderer).
+    /// Convert ingredient amounts to a unit system.
     ///
-    /// This option enables outputting some metadata content to YAML front matter instead of using
-    /// Markdown headers. Useful for static site generators.
+    /// Either "metric" or "imperial". Ingredients with unrecognized units are left unchanged.
+    #[arg(short, long)]
+    units: Option<UnitSystem>,
+
+    /// File extension of rendered files in template mode, e.g. "json".
+    ///
+    /// Defaults to the extension of the recipe template's file name, or "txt".
+    #[arg(short, long)]
+    extension: Option<String>,
+
+    /// Page size in PDF mode: "4x6" or "5x8" for recipe cards, or "a4" or "letter" for pages.
+    #[arg(short = 'p', long, default_value_t = PageSize::Letter)]
+    size: PageSize,
+
+    /// Set a template variable, e.g. `--var site=Recipes` (may be repeated).
+    ///
+    /// Only applies to the template renderer.
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var)]
+    vars: Vec<(String, String)>,
+
+    /// YAML or JSON file whose top-level keys are made available to templates as variables.
+    ///
+    /// Only applies to the template renderer.
+    #[arg(long, value_name = "FILE")]
+    context: Option<PathBuf>,
+
+    /// Use front matter instead of pure Markdown (Applies to Markdown, Rst and AsciiDoc modes).
+    ///
+    /// This option enables outputting the recipe's metadata as YAML or TOML front matter instead of
+    /// using Markdown headers. Useful for static site generators. Rst mode uses a field list and
+    /// AsciiDoc mode uses document header attributes instead.
     #[arg(short, long)]
     front_matter: bool,
+
+    /// Keys used in Markdown front matter: "sous", "hugo", "jekyll" or "zola".
+    #[arg(long, default_value_t = FrontMatterStyle::Sous)]
+    front_matter_style: FrontMatterStyle,
+
+    /// Format of Markdown front matter: "yaml" or "toml".
+    #[arg(long, default_value_t = FrontMatterFormat::Yaml)]
+    front_matter_format: FrontMatterFormat,
+
+    /// Localize labels and numbers in Markdown, template and HTML modes: "en", "de" or "fr".
+    ///
+    /// Templates can use the localized labels through the `labels` variable, and the `fraction`,
+    /// `number` and `ingredient` filters format numbers for the locale.
+    #[arg(short, long)]
+    locale: Option<Locale>,
+
+    /// Language of translated recipe text, e.g. "de". Defaults to the locale's language.
+    ///
+    /// Text without a translation in this language falls back to the recipe's own language.
+    #[arg(long)]
+    language: Option<String>,
+
+    /// Layout of ingredient lists in Markdown mode.
+    ///
+    /// Either "asterisk" or "dash" for a bulleted list, "checkbox" for a task list, or "table".
+    #[arg(long, default_value_t = IngredientStyle::Asterisk)]
+    ingredient_style: IngredientStyle,
+
+    /// List fields sous does not know about (e.g. `rating` or `notes`) in Markdown mode.
+    ///
+    /// Front matter always includes them.
+    #[arg(long)]
+    extra_fields: bool,
+
+    /// Write ingredient amounts as common fractions (e.g. "1 1/2") and pluralize units written
+    /// out as words (e.g. "2 cups") in Markdown mode.
+    #[arg(long)]
+    fractions: bool,
+
+    /// Heading level of recipe titles in Markdown mode, e.g. 2 to embed recipes in a larger
+    /// document. Sections are one level below.
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=5))]
+    heading_level: u8,
 }
 
-fn create_renderer(args: &Args) -> Result<Box<dyn Renderer>, SousError> {
-    let renderer: Box<dyn Renderer> = match args.mode {
+#[derive(Args, Debug)]
+struct ListArgs {
+    /// Cookbook directory to list.
+    #[arg()]
+    input: PathBuf,
+
+    #[command(flatten)]
+    filter: FilterArgs,
+
+    /// Keep parsed recipe information in a cache file within the Cookbook directory.
+    ///
+    /// Only recipes changed since the last cached run are parsed again.
+    #[arg(short, long)]
+    cache: bool,
+
+    /// Language of translated recipe names, e.g. "de". Defaults to each recipe's own language.
+    #[arg(long)]
+    language: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct SearchArgs {
+    /// Cookbook directory to search.
+    #[arg()]
+    input: PathBuf,
+
+    /// Search query, e.g. `curry has:chickpeas not:nuts tag:quick "total < 30m"`.
+    ///
+    /// Plain words match the name, author, labels and ingredients of a recipe. Prefixed terms
+    /// (`name:`, `author:`, `has:`, `not:`, `tag:`, `cuisine:`, `course:`, `diet:`) match a single
+    /// field, and `total`, `prep` or `cook` followed by a comparison limit the recipe's times.
+    #[arg(required = true)]
+    query: Vec<String>,
+
+    /// Output format for results.
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Maximum number of results to show.
+    #[arg(short, long)]
+    limit: Option<usize>,
+
+    /// Keep parsed recipe information in a cache file within the Cookbook directory.
+    ///
+    /// Only recipes changed since the last cached run are parsed again.
+    #[arg(short, long)]
+    cache: bool,
+
+    /// Language of translated recipe names, e.g. "de". Defaults to each recipe's own language.
+    #[arg(long)]
+    language: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct SiteArgs {
+    /// Cookbook directory to generate a site from.
+    #[arg()]
+    input: PathBuf,
+
+    /// Directory in which to output the site (will be created if necessary).
+    #[arg(short, long, default_value = "site")]
+    output: PathBuf,
+
+    /// Directory of templates overriding the built-in theme.
+    ///
+    /// Any of `base.html`, `recipe.html`, `index.html` and `tag.html` found in the directory
+    /// replace the built-in template of the same name. Other files, such as a `style.css`, are
+    /// copied into the site.
+    #[arg(short, long)]
+    theme: Option<PathBuf>,
+
+    /// Title of the site. Defaults to the title in the Cookbook manifest.
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Override number of servings.
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+    servings: Option<u32>,
+
+    /// Convert ingredient amounts to a unit system ("metric" or "imperial").
+    #[arg(short, long)]
+    units: Option<UnitSystem>,
+
+    /// Localize labels and numbers: "en", "de" or "fr".
+    #[arg(short, long)]
+    locale: Option<Locale>,
+
+    /// Language of translated recipe text, e.g. "de". Defaults to the locale's language.
+    #[arg(long)]
+    language: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct PdfArgs {
+    /// Cookbook or single recipe file to print, or `-` to read YAML from standard input.
+    ///
+    /// A single recipe is printed on its own. A Cookbook, or a YAML file of several `---`
+    /// separated recipes, is printed as one document with a title page and table of contents.
+    #[arg()]
+    input: PathBuf,
+
+    /// Output file. Defaults to the input's name with a `.pdf` extension.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Page size: "4x6" or "5x8" for recipe cards, or "a4" or "letter" for pages.
+    #[arg(short = 'p', long, default_value_t = PageSize::Letter)]
+    size: PageSize,
+
+    /// Override number of servings.
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+    servings: Option<u32>,
+
+    /// Convert ingredient amounts to a unit system ("metric" or "imperial").
+    #[arg(short, long)]
+    units: Option<UnitSystem>,
+
+    /// Language of translated recipe text, e.g. "de". Defaults to each recipe's own language.
+    #[arg(long)]
+    language: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct ShowArgs {
+    /// Recipe to show, by file name with or without its extension.
+    #[arg()]
+    recipe: String,
+
+    /// Cookbook directory containing the recipe.
+    #[arg(short = 'C', long, default_value = ".")]
+    cookbook: PathBuf,
+
+    /// Override number of servings.
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+    servings: Option<u32>,
+
+    /// Convert ingredient amounts to a unit system ("metric" or "imperial").
+    #[arg(short, long)]
+    units: Option<UnitSystem>,
+
+    /// Wrap lines at this width. Defaults to the terminal's width, or 80 columns.
+    #[arg(short, long)]
+    width: Option<usize>,
+
+    /// Never style output with colors and bold text.
+    ///
+    /// Styling is otherwise used when printing to a terminal and `NO_COLOR` is not set.
+    #[arg(long)]
+    no_color: bool,
+
+    /// Language of translated recipe text, e.g. "de". Defaults to the language of the Cookbook's
+    /// default locale, or to each recipe's own language.
+    #[arg(long)]
+    language: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct FmtArgs {
+    /// Recipe files or Cookbook directories to format.
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+
+    /// Only list files that are not formatted instead of rewriting them.
+    ///
+    /// Exits with status 1 if any file would be changed, e.g. to check recipes in CI. Files
+    /// without a `schema_version` are reported until they are formatted or migrated.
+    #[arg(long)]
+    check: bool,
+}
+
+#[derive(Args, Debug)]
+struct ConvertArgs {
+    /// Recipe file or Cookbook directory to convert, or `-` to read YAML from standard input.
+    ///
+    /// A YAML file of several `---` separated recipes is converted like a Cookbook.
+    #[arg()]
+    input: PathBuf,
+
+    /// Output path.
+    ///
+    /// A single recipe is printed to stdout unless an output file is given. A Cookbook is written
+    /// to the specified directory (will be created if necessary), along with its `cookbook.yml`
+    /// listing the converted files.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Format to convert to: "yaml", "json" or "toml".
+    ///
+    /// Defaults to the format given by the output file's extension.
+    #[arg(long)]
+    to: Option<RecipeFormat>,
+}
+
+#[derive(Args, Debug)]
+struct ImportArgs {
+    /// File or directory of files to import, or `-` to read from standard input.
+    #[arg()]
+    input: PathBuf,
+
+    /// Output path.
+    ///
+    /// A single recipe is printed to stdout unless an output file is given. The recipes of a
+    /// directory are written to the specified directory (will be created if necessary), named
+    /// after their files with a `.yml` extension.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Format of the imported files.
+    ///
+    /// Markdown files have a title heading, a bold author line and a bold line of servings and
+    /// times, followed by "Ingredients" and "Method" sections.
+    #[arg(long, value_enum, default_value_t = ImportFormat::Markdown)]
+    from: ImportFormat,
+}
+
+#[derive(Args, Debug)]
+struct MigrateArgs {
+    /// Recipe files or Cookbook directories to migrate.
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct SchemaArgs {
+    /// Write the schema to this file instead of printing it to stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct FilterArgs {
+    /// Only include recipes with this tag (may be repeated).
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+
+    /// Only include recipes of this cuisine.
+    #[arg(long)]
+    cuisine: Option<String>,
+
+    /// Only include recipes served as this course.
+    #[arg(long)]
+    course: Option<String>,
+
+    /// Only include recipes with this dietary label (may be repeated).
+    #[arg(long = "diet")]
+    dietary: Vec<String>,
+}
+
+impl From<FilterArgs> for Filter {
+    fn from(args: FilterArgs) -> Self {
+        Filter {
+            tags: args.tags,
+            cuisine: args.cuisine,
+            course: args.course,
+            dietary: args.dietary,
+        }
+    }
+}
+
+fn parse_var(var: &str) -> Result<(String, String), String> {
+    match var.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, found `{var}`")),
+    }
+}
+
+impl RenderArgs {
+    /// Language to render translated text in, defaulting to the locale's language.
+    fn language(&self) -> Option<String> {
+        self.language
+            .clone()
+            .or_else(|| self.locale.map(|locale| locale.to_string()))
+    }
+}
+
+/// Width of the terminal, if standard output is connected to one.
+fn terminal_width() -> Option<usize> {
+    terminal_size().map(|(Width(width), _)| width as usize)
+}
+
+/// Whether to style text printed to standard output, i.e. it is a TTY and `NO_COLOR` is not set.
+fn styled_output() -> bool {
+    env::var_os("NO_COLOR").is_none() && stdout().is_terminal()
+}
+
+/// Create the renderer selected by `args`. `terminal` tells whether output is printed to standard
+/// output rather than written to files.
+fn create_renderer(args: &RenderArgs, terminal: bool) -> Result<Box<dyn Renderer>, SousError> {
+    let language = args.language();
+
+    let renderer: Box<dyn Renderer> = match args.mode.clone().unwrap_or_default() {
         RenderMode::Markdown => Box::new(MarkdownRenderer {
             servings: args.servings,
+            units: args.units,
+            language: language.clone(),
             front_matter: args.front_matter,
+            front_matter_style: args.front_matter_style,
+            front_matter_format: args.front_matter_format,
+            extra_fields: args.extra_fields,
+            fractions: args.fractions,
+            labels: args.locale.unwrap_or_default().labels(),
+            locale: args.locale.unwrap_or_default(),
+            ingredient_style: args.ingredient_style,
+            title_level: args.heading_level.into(),
+            section_level: (args.heading_level + 1).into(),
             ..Default::default()
         }),
-        RenderMode::Template => match &args.template {
-            Some(path) => Box::new(TemplateRenderer::from_path(&path)?),
-            None => {
-                let mut template = String::new();
-                stdin().read_to_string(&mut template)?;
-                Box::new(TemplateRenderer::from_str(&template)?)
+        RenderMode::Template => {
+            let renderer = match (&args.theme, &args.template) {
+                (Some(theme), Some(dir)) => TemplateRenderer::builtin(theme)?.with_theme(dir)?,
+                (Some(theme), None) => TemplateRenderer::builtin(theme)?,
+                (None, Some(dir)) if dir.is_dir() => TemplateRenderer::from_dir(dir)?,
+                (None, Some(path)) => TemplateRenderer::from_path(path)?,
+                (None, None) => {
+                    let mut template = String::new();
+                    stdin().read_to_string(&mut template)?;
+                    TemplateRenderer::from_str(&template)?
+                }
+            };
+
+            let mut renderer = match &args.index_template {
+                Some(path) => renderer.with_index_path(path)?,
+                None => renderer,
+            }
+            .with_locale(args.locale.unwrap_or_default());
+            if let Some(path) = &args.context {
+                renderer = renderer.with_context_file(path)?;
+            }
+            for (key, value) in &args.vars {
+                renderer = renderer.with_var(key, value);
             }
-        },
+            renderer.servings = args.servings;
+            renderer.units = args.units;
+            renderer.language = language.clone();
+            renderer.extension = args.extension.clone();
+
+            Box::new(renderer)
+        }
+        RenderMode::Html => {
+            let mut renderer = HtmlRenderer::new()?.with_locale(args.locale.unwrap_or_default());
+            renderer.servings = args.servings;
+            renderer.units = args.units;
+            renderer.language = language.clone();
+
+            Box::new(renderer)
+        }
+        RenderMode::Rst => Box::new(RstRenderer {
+            servings: args.servings,
+            units: args.units,
+            language: language.clone(),
+            front_matter: args.front_matter,
+            labels: args.locale.unwrap_or_default().labels(),
+            ..Default::default()
+        }),
+        RenderMode::Asciidoc => Box::new(AsciiDocRenderer {
+            servings: args.servings,
+            units: args.units,
+            language: language.clone(),
+            front_matter: args.front_matter,
+            labels: args.locale.unwrap_or_default().labels(),
+            ..Default::default()
+        }),
+        RenderMode::Text => Box::new(TextRenderer {
+            width: terminal.then(terminal_width).flatten(),
+            color: terminal && styled_output(),
+            servings: args.servings,
+            units: args.units,
+            language: language.clone(),
+            labels: args.locale.unwrap_or_default().labels(),
+            ..Default::default()
+        }),
+        RenderMode::Latex => Box::new(LatexRenderer {
+            servings: args.servings,
+            units: args.units,
+            language: language.clone(),
+            labels: args.locale.unwrap_or_default().labels(),
+            ..Default::default()
+        }),
+        RenderMode::Pdf => Box::new(PdfRenderer {
+            size: args.size,
+            servings: args.servings,
+            units: args.units,
+            language: language.clone(),
+            labels: args.locale.unwrap_or_default().labels(),
+        }),
     };
 
     Ok(renderer)
 }
 
 fn main() {
-    let args = Args::parse();
+    let cli = Cli::parse();
 
-    let renderer = create_renderer(&args).unwrap_or_else(|e| {
-        eprintln!("failed to initialize renderer: {e}");
+    match cli.command {
+        Some(Command::Render(args)) => render(args),
+        Some(Command::List(args)) => list(args),
+        Some(Command::Search(args)) => search(args),
+        Some(Command::Site(args)) => site(args),
+        Some(Command::Pdf(args)) => pdf(args),
+        Some(Command::Show(args)) => show(args),
+        Some(Command::Fmt(args)) => fmt(args),
+        Some(Command::Convert(args)) => convert(args),
+        Some(Command::Import(args)) => import(args),
+        Some(Command::Migrate(args)) => migrate(args),
+        Some(Command::Schema(args)) => schema(args),
+        None => render(
+            cli.render
+                .expect("render arguments are required without a command"),
+        ),
+    }
+}
+
+/// A single recipe or a Cookbook given as input.
+enum Input {
+    Recipe(Recipe),
+    Cookbook(Cookbook),
+}
+
+/// Load a recipe file, a YAML file of `---` separated recipes or a Cookbook directory.
+///
+/// A path of `-` reads YAML from standard input.
+fn load_input(path: &Path) -> Input {
+    if path.is_dir() {
+        return Input::Cookbook(open_cookbook(path, false));
+    }
+
+    let format = RecipeFormat::from_path(path);
+    let recipes = if path == Path::new("-") {
+        Recipe::from_reader(stdin(), format)
+    } else {
+        fs::File::open(path)
+            .map_err(SousError::from)
+            .and_then(|file| Recipe::from_reader(file, format))
+    }
+    .unwrap_or_else(|e| {
+        eprintln!("failed to load recipe: {e}");
+        process::exit(1);
+    });
+
+    if recipes.is_empty() {
+        eprintln!("failed to load recipe: no recipes found");
+        process::exit(1);
+    }
+    if recipes.len() == 1 {
+        Input::Recipe(recipes.into_iter().next().unwrap())
+    } else {
+        Input::Cookbook(Cookbook::from_recipes(recipes))
+    }
+}
+
+fn open_cookbook(path: &Path, cache: bool) -> Cookbook {
+    let cookbook = Cookbook::open(path).unwrap_or_else(|e| {
+        eprintln!("failed to open cookbook: {e}");
+        process::exit(1);
+    });
+
+    if cache {
+        cookbook.with_cache()
+    } else {
+        cookbook
+    }
+}
+
+fn list(args: ListArgs) {
+    let cookbook = open_cookbook(&args.input, args.cache);
+    let filter: Filter = args.filter.into();
+
+    let summaries = load_summaries(&cookbook);
+
+    for summary in summaries.iter().filter(|s| filter.matches(&s.metadata)) {
+        let language = args
+            .language
+            .as_deref()
+            .or(summary.metadata.language.as_deref())
+            .unwrap_or(DEFAULT_LANGUAGE);
+        println!("{}: {}", summary.file, summary.metadata.localized(language));
+    }
+}
+
+/// Load a [Summary] of every recipe in the cookbook, skipping recipes that cannot be loaded with a
+/// warning.
+fn load_summaries(cookbook: &Cookbook) -> Vec<Summary> {
+    let results = cookbook.summary_results().unwrap_or_else(|e| {
+        eprintln!("failed to load recipes: {e}");
+        process::exit(1);
+    });
+
+    results
+        .into_iter()
+        .filter_map(|result| {
+            result
+                .inspect_err(|e| eprintln!("warning: skipping {e}"))
+                .ok()
+        })
+        .collect()
+}
+
+fn search(args: SearchArgs) {
+    let mut query = Query::parse(&args.query.join(" ")).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+    query.language = args.language;
+
+    let cookbook = open_cookbook(&args.input, args.cache);
+
+    let mut results = query.search_all(&load_summaries(&cookbook));
+    if let Some(limit) = args.limit {
+        results.truncate(limit);
+    }
+
+    match args.format {
+        OutputFormat::Text => {
+            for result in results {
+                println!("{}: {} by {}", result.file, result.name, result.author);
+                for snippet in result.snippets {
+                    println!("    {snippet}");
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&results).unwrap_or_else(|e| {
+                eprintln!("failed to serialize results: {e}");
+                process::exit(3);
+            });
+            println!("{json}");
+        }
+    }
+}
+
+fn site(args: SiteArgs) {
+    let cookbook = open_cookbook(&args.input, false);
+    let defaults = &cookbook.manifest().defaults;
+
+    let locale = args.locale.or(defaults.locale).unwrap_or_default();
+    let mut renderer = HtmlRenderer::new()
+        .unwrap_or_else(|e| {
+            eprintln!("failed to initialize renderer: {e}");
+            process::exit(1);
+        })
+        .with_locale(locale);
+    if let Some(theme) = &args.theme {
+        renderer = renderer.with_theme(theme).unwrap_or_else(|e| {
+            eprintln!("failed to load theme: {e}");
+            process::exit(1);
+        });
+    }
+    renderer.title = args.title;
+    renderer.servings = args.servings.or(defaults.servings);
+    renderer.units = args.units.or(defaults.units);
+    renderer.language = args.language.or_else(|| {
+        args.locale
+            .or(defaults.locale)
+            .map(|locale| locale.to_string())
+    });
+
+    Site::new(&cookbook, renderer)
+        .generate(&args.output)
+        .unwrap_or_else(|e| {
+            eprintln!("failed to generate site: {e}");
+            process::exit(2);
+        });
+}
+
+fn pdf(args: PdfArgs) {
+    let mut renderer = PdfRenderer {
+        size: args.size,
+        servings: args.servings,
+        units: args.units,
+        language: args.language,
+        ..Default::default()
+    };
+
+    let output = match load_input(&args.input) {
+        Input::Cookbook(cookbook) => {
+            let defaults = &cookbook.manifest().defaults;
+            renderer.servings = renderer.servings.or(defaults.servings);
+            renderer.units = renderer.units.or(defaults.units);
+            renderer.labels = defaults.locale.unwrap_or_default().labels();
+
+            renderer.render_cookbook(&cookbook).unwrap_or_else(|e| {
+                eprintln!("failed to render cookbook: {e}");
+                process::exit(3);
+            })
+        }
+        Input::Recipe(recipe) => renderer.render_bytes(&recipe).unwrap_or_else(|e| {
+            eprintln!("failed to render recipe: {e}");
+            process::exit(3);
+        }),
+    };
+
+    let path = args.output.unwrap_or_else(|| {
+        let name = match args.input.file_stem() {
+            Some(stem) if stem != "-" => stem,
+            _ => "cookbook".as_ref(),
+        };
+        Path::new(name).with_extension("pdf")
+    });
+    fs::write(&path, output).unwrap_or_else(|e| {
+        eprintln!("failed to write file: {e}");
+        process::exit(2);
+    });
+}
+
+fn show(args: ShowArgs) {
+    let cookbook = open_cookbook(&args.cookbook, false);
+    let defaults = &cookbook.manifest().defaults;
+
+    let recipe = cookbook.find_recipe(&args.recipe).unwrap_or_else(|e| {
+        eprintln!("failed to load recipe: {e}");
         process::exit(1);
     });
 
-    if args.input.is_dir() {
-        let cookbook = Cookbook::open(&args.input).unwrap_or_else(|e| {
-            eprintln!("failed to open cookbook: {e}");
+    let renderer = TextRenderer {
+        width: args.width.or_else(terminal_width),
+        color: !args.no_color && styled_output(),
+        servings: args.servings.or(defaults.servings),
+        units: args.units.or(defaults.units),
+        language: args
+            .language
+            .or_else(|| defaults.locale.map(|locale| locale.to_string())),
+        labels: defaults.locale.unwrap_or_default().labels(),
+        ..Default::default()
+    };
+
+    let output = renderer.render(&recipe).unwrap_or_else(|e| {
+        eprintln!("failed to render recipe: {e}");
+        process::exit(3);
+    });
+    print!("{output}");
+}
+
+/// Collect recipe files from paths to files or Cookbook directories.
+fn recipe_files(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for path in paths.iter() {
+        if path.is_dir() {
+            let cookbook = open_cookbook(path, false);
+            files.extend(cookbook.files().iter().map(|file| path.join(file)));
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    files
+}
+
+fn read_recipe_file(file: &Path) -> String {
+    fs::read_to_string(file).unwrap_or_else(|e| {
+        eprintln!("failed to read recipe {}: {e}", file.display());
+        process::exit(1);
+    })
+}
+
+/// Rewrite a recipe file's content in the canonical form of its [RecipeFormat].
+fn format_recipe(file: &Path, content: &str) -> Result<String, SousError> {
+    match RecipeFormat::from_path(file) {
+        RecipeFormat::Yaml => Recipe::format_yaml(content),
+        format => Recipe::from_format(content, format)?.to_format(format),
+    }
+}
+
+fn fmt(args: FmtArgs) {
+    let mut unformatted = false;
+    for file in recipe_files(&args.paths).iter() {
+        let content = read_recipe_file(file);
+        let formatted = format_recipe(file, &content).unwrap_or_else(|e| {
+            eprintln!("failed to format recipe {}: {e}", file.display());
             process::exit(1);
         });
 
-        let output = args.output.unwrap_or_else(|| PathBuf::from("render"));
-        if !output.is_dir() {
-            fs::create_dir(&output).unwrap_or_else(|e| {
-                eprintln!("failed to open output directory: {e}");
+        if formatted == content {
+            continue;
+        }
+        if args.check {
+            println!("{}", file.display());
+            unformatted = true;
+        } else {
+            fs::write(file, formatted).unwrap_or_else(|e| {
+                eprintln!("failed to write recipe {}: {e}", file.display());
+                process::exit(2);
             });
         }
+    }
+
+    if unformatted {
+        process::exit(1);
+    }
+}
 
-        for file in cookbook.recipes() {
-            let recipe = cookbook.load_recipe(file).unwrap_or_else(|e| {
-                eprintln!("failed to load recipe {file}: {e}");
+fn migrate(args: MigrateArgs) {
+    for file in recipe_files(&args.paths).iter() {
+        let content = read_recipe_file(file);
+        let version = RecipeFormat::from_path(file)
+            .parse_all(&content)
+            .and_then(|documents| {
+                documents
+                    .iter()
+                    .map(schema_version)
+                    .try_fold(SCHEMA_VERSION, |oldest, version| Ok(oldest.min(version?)))
+            })
+            .unwrap_or_else(|e| {
+                eprintln!("failed to load recipe {}: {e}", file.display());
                 process::exit(1);
             });
+        if version >= SCHEMA_VERSION {
+            continue;
+        }
+
+        let migrated = format_recipe(file, &content).unwrap_or_else(|e| {
+            eprintln!("failed to migrate recipe {}: {e}", file.display());
+            process::exit(1);
+        });
+        fs::write(file, migrated).unwrap_or_else(|e| {
+            eprintln!("failed to write recipe {}: {e}", file.display());
+            process::exit(2);
+        });
+        println!("{}: version {version} -> {SCHEMA_VERSION}", file.display());
+    }
+}
+
+fn convert(args: ConvertArgs) {
+    let format = args
+        .to
+        .or_else(|| args.output.as_deref().map(RecipeFormat::from_path))
+        .unwrap_or_else(|| {
+            eprintln!("no format to convert to, specify --to or an output file");
+            process::exit(1);
+        });
+    let write = |recipe: &Recipe| {
+        recipe.to_format(format).unwrap_or_else(|e| {
+            eprintln!("failed to write {format}: {e}");
+            process::exit(3);
+        })
+    };
+
+    let cookbook = match load_input(&args.input) {
+        Input::Cookbook(cookbook) => cookbook,
+        Input::Recipe(recipe) => {
+            let converted = write(&recipe);
+            match args.output {
+                Some(path) => fs::write(&path, converted).unwrap_or_else(|e| {
+                    eprintln!("failed to write file: {e}");
+                    process::exit(2);
+                }),
+                None => print!("{converted}"),
+            }
+            return;
+        }
+    };
+    let output = args.output.unwrap_or_else(|| {
+        eprintln!("an output directory is required to convert a Cookbook");
+        process::exit(1);
+    });
+    cookbook.convert(&output, format).unwrap_or_else(|e| {
+        eprintln!("failed to convert cookbook: {e}");
+        process::exit(2);
+    });
+}
+
+fn import(args: ImportArgs) {
+    let import_file = |file: &Path| {
+        let content = if file == Path::new("-") {
+            let mut content = String::new();
+            stdin().read_to_string(&mut content).map(|_| content)
+        } else {
+            fs::read_to_string(file)
+        };
+        content
+            .map_err(SousError::from)
+            .and_then(|content| args.from.parse(&content)?.to_yaml())
+    };
+
+    if !args.input.is_dir() {
+        let yaml = import_file(&args.input).unwrap_or_else(|e| {
+            eprintln!("failed to import {}: {e}", args.input.display());
+            process::exit(1);
+        });
+        match args.output {
+            Some(path) => fs::write(&path, yaml).unwrap_or_else(|e| {
+                eprintln!("failed to write file: {e}");
+                process::exit(2);
+            }),
+            None => print!("{yaml}"),
+        }
+        return;
+    }
+
+    let output = args.output.unwrap_or_else(|| {
+        eprintln!("an output directory is required to import a directory");
+        process::exit(1);
+    });
+    let mut files: Vec<PathBuf> = fs::read_dir(&args.input)
+        .and_then(|dir| dir.map(|entry| Ok(entry?.path())).collect())
+        .unwrap_or_else(|e| {
+            eprintln!("failed to read directory: {e}");
+            process::exit(1);
+        });
+    files.retain(|file| file.extension().is_some_and(|e| e == args.from.extension()));
+    files.sort();
+
+    fs::create_dir_all(&output).unwrap_or_else(|e| {
+        eprintln!("failed to create output directory: {e}");
+        process::exit(2);
+    });
+    let mut failed = 0;
+    for file in files.iter() {
+        let yaml = match import_file(file) {
+            Ok(yaml) => yaml,
+            Err(e) => {
+                eprintln!("failed to import {}: {e}", file.display());
+                failed += 1;
+                continue;
+            }
+        };
+        let path = output
+            .join(file.file_name().unwrap_or_default())
+            .with_extension(RecipeFormat::Yaml.extension());
+        fs::write(&path, yaml).unwrap_or_else(|e| {
+            eprintln!("failed to write recipe {}: {e}", path.display());
+            process::exit(2);
+        });
+    }
+    if failed > 0 {
+        eprintln!("failed to import {failed} of {} files", files.len());
+        process::exit(1);
+    }
+}
+
+fn schema(args: SchemaArgs) {
+    let schema = Recipe::json_schema();
+
+    match args.output {
+        Some(path) => fs::write(&path, schema + "\n").unwrap_or_else(|e| {
+            eprintln!("failed to write schema: {e}");
+            process::exit(2);
+        }),
+        None => println!("{schema}"),
+    }
+}
 
-            let rendered = renderer.render(&recipe).unwrap_or_else(|e| {
-                eprintln!("failed to render recipe {file}: {e}");
+fn render(mut args: RenderArgs) {
+    let reads_template = matches!(args.mode, Some(RenderMode::Template))
+        && args.theme.is_none()
+        && args.template.is_none();
+    if args.input == Path::new("-") && reads_template {
+        eprintln!("standard input cannot provide both the recipe and the template");
+        process::exit(1);
+    }
+
+    let input = load_input(&args.input);
+    if let Input::Cookbook(cookbook) = &input {
+        let defaults = &cookbook.manifest().defaults;
+        args.servings = args.servings.or(defaults.servings);
+        args.units = args.units.or(defaults.units);
+        args.locale = args.locale.or(defaults.locale);
+        if args.theme.is_none() && args.template.is_none() {
+            args.template = cookbook.default_template();
+            if args.template.is_some() {
+                args.mode = args.mode.or(Some(RenderMode::Template));
+            }
+        }
+    }
+
+    let terminal = args.output.is_none() && matches!(input, Input::Recipe(_));
+    let renderer = create_renderer(&args, terminal).unwrap_or_else(|e| {
+        eprintln!("failed to initialize renderer: {e}");
+        process::exit(1);
+    });
+    let language = args.language();
+    let labels = args.locale.unwrap_or_default().labels();
+
+    let cookbook = match input {
+        Input::Cookbook(cookbook) => cookbook,
+        Input::Recipe(recipe) => {
+            let output = renderer.render_bytes(&recipe).unwrap_or_else(|e| {
+                eprintln!("failed to render recipe: {e}");
                 process::exit(3);
             });
 
-            fs::write(
-                &output.join(PathBuf::from(file).with_extension("md")),
-                rendered,
-            )
+            match args.output {
+                Some(file) => {
+                    fs::write(&file, output).unwrap_or_else(|e| {
+                        eprintln!("failed to write file: {e}");
+                        process::exit(2);
+                    });
+                }
+                None => {
+                    stdout().write_all(&output).unwrap_or_else(|e| {
+                        eprintln!("failed to write output: {e}");
+                        process::exit(2);
+                    });
+                }
+            }
+            return;
+        }
+    };
+
+    if args.input == Path::new("-") && args.output.is_none() {
+        eprintln!("an output directory is required to render several recipes from standard input");
+        process::exit(1);
+    }
+    let output = args.output.unwrap_or_else(|| PathBuf::from("render"));
+    if !output.is_dir() {
+        fs::create_dir(&output).unwrap_or_else(|e| {
+            eprintln!("failed to open output directory: {e}");
+        });
+    }
+
+    let extension = renderer.extension().to_string();
+
+    for file in cookbook.recipes() {
+        let recipe = cookbook.load_recipe(file).unwrap_or_else(|e| {
+            eprintln!("failed to load recipe {file}: {e}");
+            process::exit(1);
+        });
+
+        recipe
+            .validate(&cookbook.manifest().vocabulary)
             .unwrap_or_else(|e| {
-                eprintln!("failed to write file for recipe {file}: {e}");
-                process::exit(2);
+                eprintln!("invalid recipe {file}: {e}");
+                process::exit(1);
             });
+        for problem in recipe.missing_translations() {
+            eprintln!("warning: recipe {file}: {problem}");
         }
-    } else {
-        let recipe = Recipe::from_file(&args.input).unwrap_or_else(|e| {
-            eprintln!("failed to load recipe: {e}");
+
+        let rendered = renderer.render_bytes(&recipe).unwrap_or_else(|e| {
+            eprintln!("failed to render recipe {file}: {e}");
+            process::exit(3);
+        });
+
+        fs::write(output.join(cookbook.page(file, &extension)), rendered).unwrap_or_else(|e| {
+            eprintln!("failed to write file for recipe {file}: {e}");
+            process::exit(2);
+        });
+    }
+
+    let index = cookbook
+        .index(&extension, language.as_deref(), &labels)
+        .unwrap_or_else(|e| {
+            eprintln!("failed to build index: {e}");
             process::exit(1);
         });
 
-        let output = renderer.render(&recipe).unwrap_or_else(|e| {
-            eprintln!("failed to render recipe: {e}");
+    let rendered = renderer.render_index(&index).unwrap_or_else(|e| {
+        eprintln!("failed to render index: {e}");
+        process::exit(3);
+    });
+
+    if let Some(rendered) = rendered {
+        fs::write(output.join(format!("index.{extension}")), rendered).unwrap_or_else(|e| {
+            eprintln!("failed to write index file: {e}");
+            process::exit(2);
+        });
+    }
+
+    let tags = cookbook
+        .tag_index(&extension, language.as_deref(), &labels)
+        .unwrap_or_else(|e| {
+            eprintln!("failed to build tag index: {e}");
+            process::exit(1);
+        });
+
+    for tag in tags.sections {
+        if tag.entries.iter().all(|entry| entry.tags.is_empty()) {
+            continue;
+        }
+
+        let rendered = renderer.render_tag(&tag).unwrap_or_else(|e| {
+            eprintln!("failed to render tag {}: {e}", tag.title);
             process::exit(3);
         });
 
-        match args.output {
-            Some(file) => {
-                fs::write(&file, output).unwrap_or_else(|e| {
-                    eprintln!("failed to write file: {e}");
+        if let Some(rendered) = rendered {
+            let dir = output.join("tags");
+            fs::create_dir_all(&dir)
+                .and_then(|_| {
+                    let page = format!("{}.{extension}", slugify(&tag.title));
+                    fs::write(dir.join(page), rendered)
+                })
+                .unwrap_or_else(|e| {
+                    eprintln!("failed to write file for tag {}: {e}", tag.title);
                     process::exit(2);
                 });
-            }
-            None => {
-                print!("{}", output);
-            }
         }
     }
 }
@@ -0,0 +1,147 @@
+//! Types for cookbook manifest files.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::units::UnitSystem;
+use crate::{Locale, SousError, Vocabulary};
+
+/// Name of the optional manifest file at the root of a cookbook directory.
+pub const MANIFEST_FILE: &str = "cookbook.yml";
+
+/// Cookbook-wide information, read from a `cookbook.yml` file.
+///
+/// ```yaml
+/// title: Family Favorites
+/// author: Cook Cookerson
+/// chapters:
+///   - title: Breakfast
+///     recipes: [pancakes, omelette]
+/// defaults:
+///   servings: 4
+///   units: metric
+/// vocabulary:
+///   dietary: [vegan, vegetarian, gluten-free]
+/// ```
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Manifest {
+    /// Optional title of the cookbook.
+    pub title: Option<String>,
+    /// Optional author or editor of the cookbook.
+    pub author: Option<String>,
+    /// Optional description of the cookbook.
+    pub description: Option<String>,
+    /// Ordered list of [Chapter]s.
+    pub chapters: Vec<Chapter>,
+    /// Default render settings for the cookbook.
+    pub defaults: Defaults,
+    /// Allowed recipe labels within the cookbook.
+    pub vocabulary: Vocabulary,
+}
+
+/// A named, ordered group of recipes within a cookbook.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Chapter {
+    /// Display title of the chapter.
+    pub title: String,
+    /// Recipes in the chapter, in order.
+    ///
+    /// Entries may be given with or without the file extension.
+    #[serde(default)]
+    pub recipes: Vec<String>,
+}
+
+/// Default render settings applied to every recipe in a cookbook.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Defaults {
+    /// Optionally override the serving count of every recipe.
+    pub servings: Option<u32>,
+    /// Optional [UnitSystem] to convert ingredient amounts into.
+    pub units: Option<UnitSystem>,
+    /// Optional [Locale] to localize labels and numbers into.
+    pub locale: Option<Locale>,
+    /// Optional template file, relative to the cookbook directory. The CLI renders in template
+    /// mode when it is set, unless another mode is given.
+    pub template: Option<PathBuf>,
+}
+
+impl Manifest {
+    /// Create a new, empty manifest.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Load a manifest from the provided YAML string slice.
+    pub fn from_yaml(content: &str) -> Result<Manifest, SousError> {
+        Ok(serde_yaml::from_str(content)?)
+    }
+
+    /// Load a manifest from the provided file path.
+    pub fn from_file(path: &Path) -> Result<Manifest, SousError> {
+        let content = fs::read_to_string(path)?;
+
+        Self::from_yaml(&content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_yaml() -> Result<(), SousError> {
+        let yaml = "
+title: test cookbook
+author: test author
+chapters:
+  - title: test chapter
+    recipes:
+      - recipe one
+      - recipe-two.yml
+defaults:
+  servings: 4
+  units: imperial
+  locale: de
+  template: template.md
+vocabulary:
+  courses: [main, dessert]
+        ";
+
+        let manifest = Manifest::from_yaml(yaml)?;
+        assert_eq!(manifest.title.as_deref(), Some("test cookbook"));
+        assert_eq!(manifest.author.as_deref(), Some("test author"));
+        assert_eq!(manifest.description, None);
+        assert_eq!(
+            manifest.chapters,
+            vec![Chapter {
+                title: "test chapter".to_string(),
+                recipes: vec!["recipe one".to_string(), "recipe-two.yml".to_string()],
+            }]
+        );
+        assert_eq!(manifest.defaults.servings, Some(4));
+        assert_eq!(manifest.defaults.units, Some(UnitSystem::Imperial));
+        assert_eq!(manifest.defaults.locale, Some(Locale::German));
+        assert_eq!(
+            manifest.defaults.template,
+            Some(PathBuf::from("template.md"))
+        );
+        assert_eq!(
+            manifest.vocabulary.courses,
+            Some(vec!["main".to_string(), "dessert".to_string()])
+        );
+        assert_eq!(manifest.vocabulary.tags, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_yaml_empty() -> Result<(), SousError> {
+        let manifest = Manifest::from_yaml("title: test cookbook")?;
+        assert_eq!(manifest.chapters, vec![]);
+        assert_eq!(manifest.defaults, Defaults::default());
+        Ok(())
+    }
+}
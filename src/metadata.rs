@@ -1,25 +1,51 @@
 //! Types for recipe meta information.
 
+use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Display;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use crate::translation::{Text, DEFAULT_LANGUAGE};
+use crate::SousError;
 
 /// Container for recipe meta information.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Metadata {
     /// Display name for the recipe.
-    pub name: String,
+    pub name: Text,
     /// Original author of the recipe.
     pub author: String,
     /// Servings yielded by the recipe as written.
     pub servings: u32,
     /// Optional URL source of the recipe.
     pub url: Option<String>,
+    /// Optional path or URL of a photo of the dish.
+    pub image: Option<String>,
     /// Optional time in minutes estimated for prep.
     pub prep_minutes: Option<u32>,
     /// Time in minutes estimated for cooking.
     pub cook_minutes: u32,
+    /// Free-form tags describing the recipe.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Optional cuisine the recipe belongs to (e.g. "italian").
+    pub cuisine: Option<String>,
+    /// Optional course the recipe is served as (e.g. "dessert").
+    pub course: Option<String>,
+    /// Dietary labels that apply to the recipe (e.g. "vegan", "gluten-free").
+    #[serde(default)]
+    pub dietary: Vec<String>,
+    /// Optional language code of the recipe's plain text (e.g. "de"), also used when a translation
+    /// is missing. Defaults to English.
+    pub language: Option<String>,
+    /// Fields sous does not know about (e.g. a `rating` or `notes`), kept as they were loaded and
+    /// written back after the known fields.
+    #[serde(flatten)]
+    #[schemars(with = "BTreeMap<String, serde_json::Value>")]
+    pub extra: BTreeMap<String, Value>,
 }
 
 impl Metadata {
@@ -27,6 +53,50 @@ impl Metadata {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Total estimated time in minutes, including prep.
+    pub fn total_minutes(&self) -> u32 {
+        self.prep_minutes.unwrap_or(0) + self.cook_minutes
+    }
+
+    /// Create a copy of the metadata with its name resolved into `language`, falling back to the
+    /// metadata's own [language](Metadata::language) or [DEFAULT_LANGUAGE].
+    pub fn localized(&self, language: &str) -> Metadata {
+        let fallback = self.language.as_deref().unwrap_or(DEFAULT_LANGUAGE);
+
+        Metadata {
+            name: self.name.localized(language, fallback),
+            ..self.clone()
+        }
+    }
+
+    /// Check the metadata's labels against the provided [Vocabulary].
+    ///
+    /// Returns a [SousError::ValidationError] listing every label not found in the vocabulary.
+    pub fn validate(&self, vocabulary: &Vocabulary) -> Result<(), SousError> {
+        let mut problems: Vec<String> = Vec::new();
+
+        let mut check = |field: &str, allowed: &Option<Vec<String>>, values: &[String]| {
+            if let Some(allowed) = allowed {
+                for value in values {
+                    if !allowed.iter().any(|a| a.eq_ignore_ascii_case(value)) {
+                        problems.push(format!("unknown {field} '{value}'"));
+                    }
+                }
+            }
+        };
+
+        check("tag", &vocabulary.tags, &self.tags);
+        check("cuisine", &vocabulary.cuisines, self.cuisine.as_slice());
+        check("course", &vocabulary.courses, self.course.as_slice());
+        check("dietary label", &vocabulary.dietary, &self.dietary);
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(SousError::ValidationError(problems))
+        }
+    }
 }
 
 impl Display for Metadata {
@@ -34,3 +104,118 @@ impl Display for Metadata {
         write!(f, "{} by {}", self.name, self.author)
     }
 }
+
+/// Allowed values for recipe labels.
+///
+/// Each list is optional; labels of a kind without a list are not restricted.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Vocabulary {
+    /// Allowed tags.
+    pub tags: Option<Vec<String>>,
+    /// Allowed cuisines.
+    pub cuisines: Option<Vec<String>>,
+    /// Allowed courses.
+    pub courses: Option<Vec<String>>,
+    /// Allowed dietary labels.
+    pub dietary: Option<Vec<String>>,
+}
+
+/// Criteria for selecting recipes by their labels.
+///
+/// Empty criteria match every recipe. Comparisons ignore ASCII case.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Filter {
+    /// Tags that must all be present.
+    pub tags: Vec<String>,
+    /// Cuisine that must match, if any.
+    pub cuisine: Option<String>,
+    /// Course that must match, if any.
+    pub course: Option<String>,
+    /// Dietary labels that must all be present.
+    pub dietary: Vec<String>,
+}
+
+impl Filter {
+    /// Create a new, empty filter.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Check whether the provided [Metadata] satisfies the filter.
+    pub fn matches(&self, metadata: &Metadata) -> bool {
+        let contains = |values: &[String], wanted: &String| {
+            values.iter().any(|v| v.eq_ignore_ascii_case(wanted))
+        };
+        let equals = |value: &Option<String>, wanted: &Option<String>| match wanted {
+            Some(wanted) => value
+                .as_ref()
+                .is_some_and(|v| v.eq_ignore_ascii_case(wanted)),
+            None => true,
+        };
+
+        self.tags.iter().all(|tag| contains(&metadata.tags, tag))
+            && self
+                .dietary
+                .iter()
+                .all(|label| contains(&metadata.dietary, label))
+            && equals(&metadata.cuisine, &self.cuisine)
+            && equals(&metadata.course, &self.course)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gen_metadata() -> Metadata {
+        Metadata {
+            tags: vec!["quick".to_string(), "Summer".to_string()],
+            cuisine: Some("italian".to_string()),
+            course: Some("main".to_string()),
+            dietary: vec!["vegan".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let metadata = gen_metadata();
+        let vocabulary = Vocabulary {
+            tags: Some(vec!["quick".to_string(), "summer".to_string()]),
+            dietary: Some(vec!["vegetarian".to_string()]),
+            ..Default::default()
+        };
+
+        match metadata.validate(&vocabulary) {
+            Err(SousError::ValidationError(problems)) => {
+                assert_eq!(problems, vec!["unknown dietary label 'vegan'"])
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert!(metadata.validate(&Vocabulary::default()).is_ok());
+    }
+
+    #[test]
+    fn test_filter() {
+        let metadata = gen_metadata();
+
+        assert!(Filter::new().matches(&metadata));
+        assert!(Filter {
+            tags: vec!["summer".to_string()],
+            cuisine: Some("Italian".to_string()),
+            ..Default::default()
+        }
+        .matches(&metadata));
+        assert!(!Filter {
+            tags: vec!["quick".to_string(), "winter".to_string()],
+            ..Default::default()
+        }
+        .matches(&metadata));
+        assert!(!Filter {
+            course: Some("dessert".to_string()),
+            ..Default::default()
+        }
+        .matches(&metadata));
+    }
+}
@@ -0,0 +1,92 @@
+//! Upgrades of recipe documents written for older versions of the recipe format.
+
+use serde_yaml::Value;
+
+use crate::SousError;
+
+/// Version of the recipe format written by this version of sous, stored in recipe files as
+/// `schema_version`.
+///
+/// Files without a `schema_version` predate versioning and are treated as version 0.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Upgrades a document by one version, from the version given by its index in [MIGRATIONS].
+type Migration = fn(&mut Value) -> Result<(), SousError>;
+
+/// Migrations in order, the first upgrading version 0 to version 1.
+///
+/// An incompatible change to the recipe format bumps [SCHEMA_VERSION] and appends a migration
+/// rewriting older documents into the new structure.
+const MIGRATIONS: [Migration; SCHEMA_VERSION as usize] = [v0_to_v1];
+
+/// Get the `schema_version` of a recipe document, or 0 if it has none.
+///
+/// Returns [SousError::UnsupportedSchemaVersion] if the version is not a non-negative integer.
+pub fn schema_version(document: &Value) -> Result<u32, SousError> {
+    match document.get("schema_version") {
+        None => Ok(0),
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| {
+                SousError::UnsupportedSchemaVersion(
+                    serde_yaml::to_string(version)
+                        .unwrap_or_default()
+                        .trim_end()
+                        .to_string(),
+                )
+            }),
+    }
+}
+
+/// Upgrade a recipe document to [SCHEMA_VERSION] in place, returning the version it was written
+/// for.
+///
+/// Returns [SousError::UnsupportedSchemaVersion] for documents written for a newer version of the
+/// format than this version of sous supports.
+pub fn migrate(document: &mut Value) -> Result<u32, SousError> {
+    let version = schema_version(document)?;
+    if version > SCHEMA_VERSION {
+        return Err(SousError::UnsupportedSchemaVersion(version.to_string()));
+    }
+
+    for migration in MIGRATIONS[version as usize..].iter() {
+        migration(document)?;
+    }
+    if let Some(mapping) = document.as_mapping_mut() {
+        mapping.insert(Value::from("schema_version"), Value::from(SCHEMA_VERSION));
+    }
+
+    Ok(version)
+}
+
+/// Files written before versioning share the structure of version 1, which only adds the
+/// `schema_version` key itself.
+fn v0_to_v1(_document: &mut Value) -> Result<(), SousError> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_unversioned() {
+        let mut document: Value = serde_yaml::from_str("name: test recipe").unwrap();
+
+        assert_eq!(migrate(&mut document).unwrap(), 0);
+        assert_eq!(schema_version(&document).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_unsupported() {
+        let mut newer: Value = serde_yaml::from_str("schema_version: 99").unwrap();
+        assert!(matches!(
+            migrate(&mut newer),
+            Err(SousError::UnsupportedSchemaVersion(version)) if version == "99"
+        ));
+
+        let invalid: Value = serde_yaml::from_str("schema_version: latest").unwrap();
+        assert!(schema_version(&invalid).is_err());
+    }
+}
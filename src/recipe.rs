@@ -1,24 +1,38 @@
 //! Types for representing culinary recipes.
 
+use std::collections::BTreeSet;
+use std::fmt;
 use std::fs;
+use std::io::Read;
 use std::path::Path;
+use std::str::FromStr;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
 
+use crate::import;
 use crate::ingredient::Ingredient;
 use crate::metadata::Metadata;
-use crate::SousError;
+use crate::migration::{migrate, SCHEMA_VERSION};
+use crate::translation::{Text, DEFAULT_LANGUAGE};
+use crate::units::normalize_unit;
+use crate::{SousError, UnitSystem, Vocabulary};
 
 /// A culinary recipe describing how to make a dish.
-#[derive(Clone, Debug, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, JsonSchema)]
 pub struct Recipe {
+    /// Version of the recipe format, the latest version for new and loaded recipes.
+    #[serde(default)]
+    #[schemars(range(max = "SCHEMA_VERSION"))]
+    pub schema_version: u32,
     /// Recipe [Metadata].
     #[serde(flatten)]
     pub metadata: Metadata,
-    /// List of steps required to make the dish.
-    pub steps: Vec<String>,
-    /// List of [Ingredient]s required to make the dish.
+    /// List of ingredients required to make the dish.
     pub ingredients: Vec<Ingredient>,
+    /// List of steps required to make the dish.
+    pub steps: Vec<Text>,
 }
 
 impl Recipe {
@@ -28,15 +42,510 @@ impl Recipe {
     }
 
     /// Load a recipe from the provided YAML string slice.
+    ///
+    /// Recipes written for an older version of the format are [migrated](migrate) first.
     pub fn from_yaml(content: &str) -> Result<Recipe, SousError> {
-        Ok(serde_yaml::from_str(content)?)
+        Recipe::from_format(content, RecipeFormat::Yaml)
+    }
+
+    /// Load every recipe from a YAML stream of `---` separated documents, in order.
+    ///
+    /// Each document is [migrated](migrate) on its own, and empty documents are skipped.
+    pub fn from_yaml_stream(content: &str) -> Result<Vec<Recipe>, SousError> {
+        let mut recipes = Vec::new();
+        for document in serde_yaml::Deserializer::from_str(content) {
+            let mut document = Value::deserialize(document)?;
+            if document.is_null() {
+                continue;
+            }
+            migrate(&mut document)?;
+            recipes.push(serde_yaml::from_value(document)?);
+        }
+
+        Ok(recipes)
+    }
+
+    /// Load a recipe from the provided JSON string slice, [migrating](migrate) it like
+    /// [Recipe::from_yaml].
+    pub fn from_json(content: &str) -> Result<Recipe, SousError> {
+        Recipe::from_format(content, RecipeFormat::Json)
+    }
+
+    /// Load a recipe from the provided TOML string slice, [migrating](migrate) it like
+    /// [Recipe::from_yaml].
+    pub fn from_toml(content: &str) -> Result<Recipe, SousError> {
+        Recipe::from_format(content, RecipeFormat::Toml)
+    }
+
+    /// Import a recipe from Markdown written in roughly the layout of
+    /// [MarkdownRenderer](crate::MarkdownRenderer).
+    ///
+    /// The first heading is the recipe's name, followed by a bold author line (optionally with the
+    /// URL after a `|`), a bold line of servings and times, and `* key: value` lines read as
+    /// [Metadata::extra] fields. Ingredient lines are listed or tabled under an "Ingredients"
+    /// heading and [parsed](Ingredient::from_str), and steps are listed under a "Method" heading,
+    /// with labels of every [Locale](crate::Locale) recognized. YAML or TOML front matter may
+    /// provide the metadata instead. Missing authors and times are left empty, and a missing
+    /// serving count defaults to 1.
+    ///
+    /// Returns [SousError::ImportError] if the document has no title or an ingredient line cannot
+    /// be parsed, naming the line.
+    pub fn from_markdown(content: &str) -> Result<Recipe, SousError> {
+        import::markdown(content)
+    }
+
+    /// Load a recipe from a string slice in the given [RecipeFormat], [migrating](migrate) it like
+    /// [Recipe::from_yaml].
+    pub fn from_format(content: &str, format: RecipeFormat) -> Result<Recipe, SousError> {
+        let mut document = format.parse(content)?;
+        migrate(&mut document)?;
+
+        Ok(serde_yaml::from_value(document)?)
+    }
+
+    /// Generate a JSON Schema of the recipe format, e.g. for validation and autocompletion in
+    /// editors.
+    ///
+    /// The schema describes YAML, JSON and TOML recipe files alike, and is published as
+    /// `schema/recipe.schema.json`.
+    pub fn json_schema() -> String {
+        let schema = schemars::schema_for!(Recipe);
+        serde_json::to_string_pretty(&schema).expect("JSON Schema should serialize")
+    }
+
+    /// Write the recipe as YAML in a canonical form.
+    ///
+    /// Keys follow the order of the recipe's fields, with [Metadata::extra] fields after the known
+    /// metadata fields and ingredients before steps. Unset fields and empty label lists are left
+    /// out, units are written by their [canonical names](normalize_unit) and amounts are written as
+    /// integers when whole, otherwise as the shortest decimal that reads back as the same amount.
+    pub fn to_yaml(&self) -> Result<String, SousError> {
+        Ok(serde_yaml::to_string(&self.canonical()?)?)
+    }
+
+    /// Write the recipe as pretty-printed JSON in the canonical form of [Recipe::to_yaml].
+    pub fn to_json(&self) -> Result<String, SousError> {
+        Ok(serde_json::to_string_pretty(&self.canonical()?)? + "\n")
+    }
+
+    /// Write the recipe as TOML in the canonical form of [Recipe::to_yaml].
+    ///
+    /// Ingredients follow the metadata and steps as an array of `[[ingredients]]` tables.
+    pub fn to_toml(&self) -> Result<String, SousError> {
+        Ok(toml::to_string(&self.canonical()?)?)
+    }
+
+    /// Write the recipe in the given [RecipeFormat].
+    pub fn to_format(&self, format: RecipeFormat) -> Result<String, SousError> {
+        match format {
+            RecipeFormat::Yaml => self.to_yaml(),
+            RecipeFormat::Json => self.to_json(),
+            RecipeFormat::Toml => self.to_toml(),
+        }
+    }
+
+    /// Rewrite a recipe given as YAML in the canonical form of [Recipe::to_yaml].
+    ///
+    /// Comments before the first key are kept, as are keys that are not ingredient fields, which
+    /// are moved to the end of their ingredient. Unknown recipe fields are kept as
+    /// [Metadata::extra]. Any other comments are lost.
+    ///
+    /// A stream of `---` separated recipes is formatted document by document, keeping empty and
+    /// comment-only documents as they are.
+    pub fn format_yaml(content: &str) -> Result<String, SousError> {
+        let documents = split_documents(content);
+        if documents.len() == 1 {
+            return Recipe::format_document(content);
+        }
+
+        let mut output = String::new();
+        for (i, document) in documents.into_iter().enumerate() {
+            if i > 0 {
+                output.push_str("---\n");
+            }
+            if is_empty_document(document) {
+                output.push_str(document);
+            } else {
+                output.push_str(&Recipe::format_document(document)?);
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Rewrite a single YAML document as described for [Recipe::format_yaml].
+    fn format_document(content: &str) -> Result<String, SousError> {
+        let recipe = Recipe::from_yaml(content)?;
+        let original: Value = serde_yaml::from_str(content)?;
+        let mut formatted = recipe.canonical()?;
+
+        if let (Some(original), Some(formatted)) = (
+            original["ingredients"].as_sequence(),
+            formatted["ingredients"].as_sequence_mut(),
+        ) {
+            let known = serde_yaml::to_value(Ingredient::default())?;
+            for (original, formatted) in original.iter().zip(formatted.iter_mut()) {
+                keep_unknown(original, formatted, &known);
+            }
+        }
+
+        let mut header: Vec<&str> = content
+            .lines()
+            .skip_while(|line| line.trim().is_empty())
+            .take_while(|line| line.trim().is_empty() || line.trim_start().starts_with('#'))
+            .collect();
+        while header.len() > 1 && header[header.len() - 2].trim().is_empty() {
+            header.pop();
+        }
+
+        let mut output = String::new();
+        for line in header {
+            output.push_str(line.trim_end());
+            output.push('\n');
+        }
+        output.push_str(&serde_yaml::to_string(&formatted)?);
+        Ok(output)
+    }
+
+    /// Serialize the recipe into the canonical [Value] written by [Recipe::to_yaml].
+    fn canonical(&self) -> Result<Value, SousError> {
+        let mut value = serde_yaml::to_value(self)?;
+
+        if let Some(values) = value["ingredients"].as_sequence_mut() {
+            for (ingredient, value) in self.ingredients.iter().zip(values.iter_mut()) {
+                if let Some(amount) = ingredient.amount {
+                    value["amount"] = amount_value(amount);
+                }
+                if let Some(unit) = &ingredient.unit {
+                    value["unit"] = Value::from(normalize_unit(unit));
+                }
+            }
+        }
+
+        if let Some(mapping) = value.as_mapping_mut() {
+            mapping.retain(|key, value| {
+                let label = matches!(key.as_str(), Some("tags" | "dietary"));
+                !(label && value.as_sequence().is_some_and(Vec::is_empty))
+            });
+        }
+        remove_nulls(&mut value);
+
+        Ok(value)
+    }
+
+    /// Create a copy of the recipe scaled to the given number of servings.
+    ///
+    /// Ingredient amounts are multiplied by the ratio of new to original servings. Recipes without
+    /// a serving count are only given the new count.
+    pub fn scaled(&self, servings: u32) -> Recipe {
+        let mut recipe = self.clone();
+        recipe.metadata.servings = servings;
+
+        if self.metadata.servings > 0 {
+            let multiplier = servings as f32 / self.metadata.servings as f32;
+            for ingredient in recipe.ingredients.iter_mut() {
+                ingredient.amount = ingredient.amount.map(|amount| amount * multiplier);
+            }
+        }
+
+        recipe
+    }
+
+    /// Create a copy of the recipe with ingredient amounts converted into the given [UnitSystem].
+    pub fn to_system(&self, system: UnitSystem) -> Recipe {
+        Recipe {
+            ingredients: self
+                .ingredients
+                .iter()
+                .map(|ingredient| ingredient.to_system(system))
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Language of the recipe's plain text, used when a translation is missing.
+    ///
+    /// Set by [Metadata::language], defaulting to [DEFAULT_LANGUAGE].
+    pub fn default_language(&self) -> &str {
+        self.metadata
+            .language
+            .as_deref()
+            .unwrap_or(DEFAULT_LANGUAGE)
+    }
+
+    /// Create a copy of the recipe with every translated [Text] resolved into `language`, falling
+    /// back to the recipe's [default language](Recipe::default_language).
+    pub fn localized(&self, language: &str) -> Recipe {
+        let fallback = self.default_language();
+        let mut recipe = self.clone();
+
+        recipe.metadata = self.metadata.localized(language);
+        for step in recipe.steps.iter_mut() {
+            *step = step.localized(language, fallback);
+        }
+        for ingredient in recipe.ingredients.iter_mut() {
+            ingredient.name = ingredient.name.localized(language, fallback);
+        }
+
+        recipe
+    }
+
+    /// Check the recipe's labels against the provided [Vocabulary].
+    ///
+    /// Missing translations are not an error, as text falls back to the recipe's default
+    /// language. See [Recipe::missing_translations] to list them.
+    pub fn validate(&self, vocabulary: &Vocabulary) -> Result<(), SousError> {
+        self.metadata.validate(vocabulary)
+    }
+
+    /// List the texts of the recipe that are missing a translation, e.g. "missing 'de'
+    /// translation of step 1".
+    ///
+    /// Once any text is translated, every text is expected in each language used throughout the
+    /// recipe. Plain text counts as the recipe's default language.
+    pub fn missing_translations(&self) -> Vec<String> {
+        let mut texts = vec![("name".to_string(), &self.metadata.name)];
+        for (i, step) in self.steps.iter().enumerate() {
+            texts.push((format!("step {}", i + 1), step));
+        }
+        for ingredient in self.ingredients.iter() {
+            texts.push((
+                format!("ingredient '{}'", ingredient.name),
+                &ingredient.name,
+            ));
+        }
+
+        let mut languages: BTreeSet<&str> = texts
+            .iter()
+            .flat_map(|(_, text)| text.languages())
+            .collect();
+        if !languages.is_empty() {
+            languages.insert(self.default_language());
+        }
+
+        let mut missing = Vec::new();
+        for (field, text) in texts.iter() {
+            let translated = match text {
+                Text::Plain(_) => vec![self.default_language()],
+                Text::Translated(_) => text.languages(),
+            };
+            for language in languages.iter() {
+                if !translated.contains(language) {
+                    missing.push(format!("missing '{language}' translation of {field}"));
+                }
+            }
+        }
+
+        missing
     }
 
-    /// Load a recipe from the provided file path.
+    /// Load every recipe in the given [RecipeFormat] from a reader, such as standard input.
+    ///
+    /// YAML may hold a stream of `---` separated recipes as read by [Recipe::from_yaml_stream].
+    /// Other formats hold a single recipe.
+    pub fn from_reader<R: Read>(
+        mut reader: R,
+        format: RecipeFormat,
+    ) -> Result<Vec<Recipe>, SousError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        match format {
+            RecipeFormat::Yaml => Self::from_yaml_stream(&content),
+            format => Ok(vec![Self::from_format(&content, format)?]),
+        }
+    }
+
+    /// Load a recipe from the provided file path, in the [RecipeFormat] given by its extension.
     pub fn from_file(path: &Path) -> Result<Recipe, SousError> {
         let content = fs::read_to_string(path)?;
 
-        Ok(Self::from_yaml(&content)?)
+        Self::from_format(&content, RecipeFormat::from_path(path))
+    }
+}
+
+impl Default for Recipe {
+    fn default() -> Self {
+        Recipe {
+            schema_version: SCHEMA_VERSION,
+            metadata: Metadata::default(),
+            ingredients: Vec::new(),
+            steps: Vec::new(),
+        }
+    }
+}
+
+/// File format of a recipe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum RecipeFormat {
+    /// YAML, read from `.yml` and `.yaml` files.
+    #[default]
+    Yaml,
+    /// JSON, read from `.json` files.
+    Json,
+    /// TOML, read from `.toml` files.
+    Toml,
+}
+
+impl RecipeFormat {
+    /// Get the format of a file by its extension, if it is a recipe file extension.
+    pub fn from_extension(extension: &str) -> Option<RecipeFormat> {
+        match extension.to_lowercase().as_str() {
+            "yml" | "yaml" => Some(RecipeFormat::Yaml),
+            "json" => Some(RecipeFormat::Json),
+            "toml" => Some(RecipeFormat::Toml),
+            _ => None,
+        }
+    }
+
+    /// Get the format of a file by its extension, defaulting to YAML.
+    pub fn from_path(path: &Path) -> RecipeFormat {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(RecipeFormat::from_extension)
+            .unwrap_or_default()
+    }
+
+    /// Parse a document in the format into a [Value], without interpreting it as a recipe.
+    pub fn parse(&self, content: &str) -> Result<Value, SousError> {
+        Ok(match self {
+            RecipeFormat::Yaml => serde_yaml::from_str(content)?,
+            RecipeFormat::Json => serde_json::from_str(content)?,
+            RecipeFormat::Toml => toml::from_str(content)?,
+        })
+    }
+
+    /// Parse every document in the format into a [Value] like [RecipeFormat::parse].
+    ///
+    /// YAML may hold a stream of `---` separated documents, of which empty ones are skipped. Other
+    /// formats hold a single document.
+    pub fn parse_all(&self, content: &str) -> Result<Vec<Value>, SousError> {
+        if *self != RecipeFormat::Yaml {
+            return Ok(vec![self.parse(content)?]);
+        }
+
+        let mut documents = Vec::new();
+        for document in serde_yaml::Deserializer::from_str(content) {
+            let document = Value::deserialize(document)?;
+            if !document.is_null() {
+                documents.push(document);
+            }
+        }
+
+        Ok(documents)
+    }
+
+    /// Extension of files written in the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RecipeFormat::Yaml => "yml",
+            RecipeFormat::Json => "json",
+            RecipeFormat::Toml => "toml",
+        }
+    }
+}
+
+impl fmt::Display for RecipeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecipeFormat::Yaml => write!(f, "yaml"),
+            RecipeFormat::Json => write!(f, "json"),
+            RecipeFormat::Toml => write!(f, "toml"),
+        }
+    }
+}
+
+impl FromStr for RecipeFormat {
+    type Err = SousError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RecipeFormat::from_extension(s).ok_or_else(|| SousError::UnknownRecipeFormat(s.to_string()))
+    }
+}
+
+/// Split YAML content into the text of its `---` separated documents, keeping line endings.
+///
+/// Content without separators is a single document. Content starting with a separator has an
+/// empty first document.
+pub(crate) fn split_documents(content: &str) -> Vec<&str> {
+    let mut documents = Vec::new();
+    let mut start = 0;
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let marker = line.trim_end();
+        if marker == "---" || marker.starts_with("--- #") {
+            documents.push(&content[start..offset]);
+            start = offset + line.len();
+        }
+        offset += line.len();
+    }
+    documents.push(&content[start..]);
+
+    documents
+}
+
+/// Split the content of a recipe file into the text of the documents holding one recipe each.
+///
+/// YAML content of several `---` separated documents, not counting empty ones, has one recipe
+/// per document. Other content, like files of other formats, holds a single recipe.
+pub(crate) fn recipe_documents(content: &str, format: RecipeFormat) -> Vec<&str> {
+    if format == RecipeFormat::Yaml {
+        let documents: Vec<&str> = split_documents(content)
+            .into_iter()
+            .filter(|document| !is_empty_document(document))
+            .collect();
+        if documents.len() > 1 {
+            return documents;
+        }
+    }
+
+    vec![content]
+}
+
+/// Whether a YAML document holds nothing but blank lines and comments.
+pub(crate) fn is_empty_document(document: &str) -> bool {
+    document
+        .lines()
+        .map(str::trim)
+        .all(|line| line.is_empty() || line.starts_with('#'))
+}
+
+/// Write an amount as an integer when whole, otherwise as the shortest decimal that reads back as
+/// the same [f32].
+fn amount_value(amount: f32) -> Value {
+    let exact: f64 = amount.to_string().parse().unwrap_or(amount as f64);
+    if exact.fract() == 0.0 && exact.abs() < i64::MAX as f64 {
+        Value::from(exact as i64)
+    } else {
+        Value::from(exact)
+    }
+}
+
+/// Remove null entries from every mapping within `value`.
+fn remove_nulls(value: &mut Value) {
+    match value {
+        Value::Mapping(mapping) => {
+            mapping.retain(|_, value| !value.is_null());
+            mapping.values_mut().for_each(remove_nulls);
+        }
+        Value::Sequence(values) => values.iter_mut().for_each(remove_nulls),
+        _ => {}
+    }
+}
+
+/// Copy entries of the `original` mapping to the end of `formatted` when their keys are not found
+/// in the `known` mapping.
+fn keep_unknown(original: &Value, formatted: &mut Value, known: &Value) {
+    if let (Some(original), Some(formatted)) = (original.as_mapping(), formatted.as_mapping_mut()) {
+        for (key, value) in original.iter() {
+            if !known
+                .as_mapping()
+                .is_some_and(|known| known.contains_key(key))
+            {
+                formatted.insert(key.clone(), value.clone());
+            }
+        }
     }
 }
 
@@ -69,14 +578,331 @@ ingredients:
         assert_eq!(
             recipe.ingredients,
             vec![Ingredient {
-                name: "test ingredient".to_string(),
+                name: "test ingredient".into(),
                 amount: Some(1.0),
-                unit: Some("test unit".to_string())
+                unit: Some("test unit".to_string()),
+                recipe: None,
             }]
         );
         Ok(())
     }
 
+    #[test]
+    fn test_localized() -> Result<(), SousError> {
+        let yaml = "
+name: {en: pancakes, de: Pfannkuchen}
+author: test author
+servings: 1
+cook_minutes: 1
+language: en
+steps:
+  - {en: mix, de: mischen}
+  - fry
+ingredients:
+  - name: {en: flour, de: Mehl}
+        ";
+
+        let recipe = Recipe::from_yaml(yaml)?;
+        assert_eq!(recipe.default_language(), "en");
+
+        let german = recipe.localized("de");
+        assert_eq!(german.metadata.name, Text::from("Pfannkuchen"));
+        assert_eq!(german.steps, vec![Text::from("mischen"), Text::from("fry")]);
+        assert_eq!(german.ingredients[0].name, Text::from("Mehl"));
+
+        let french = recipe.localized("fr");
+        assert_eq!(french.metadata.name, Text::from("pancakes"));
+
+        assert!(recipe.validate(&Vocabulary::default()).is_ok());
+        assert_eq!(
+            recipe.missing_translations(),
+            vec!["missing 'de' translation of step 2"]
+        );
+        assert!(german.missing_translations().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_yaml() -> Result<(), SousError> {
+        let recipe = Recipe {
+            metadata: Metadata {
+                name: "test recipe".into(),
+                author: "test author".to_string(),
+                servings: 2,
+                cook_minutes: 5,
+                ..Default::default()
+            },
+            ingredients: vec![
+                Ingredient {
+                    name: "flour".into(),
+                    amount: Some(2.0),
+                    unit: Some("Cups".to_string()),
+                    ..Default::default()
+                },
+                Ingredient {
+                    name: "salt".into(),
+                    amount: Some(1.0 / 3.0),
+                    unit: Some("pinch".to_string()),
+                    ..Default::default()
+                },
+            ],
+            steps: vec!["mix".into()],
+            ..Default::default()
+        };
+
+        let yaml = recipe.to_yaml()?;
+        assert_eq!(
+            yaml,
+            "schema_version: 1\nname: test recipe\nauthor: test author\nservings: 2\ncook_minutes: 5\n\
+             ingredients:\n- name: flour\n  amount: 2\n  unit: cup\n\
+             - name: salt\n  amount: 0.33333334\n  unit: pinch\nsteps:\n- mix\n"
+        );
+        assert_eq!(Recipe::from_yaml(&yaml)?.to_yaml()?, yaml);
+
+        let mut small = recipe.clone();
+        small.ingredients[0].amount = Some(0.0004);
+        small.ingredients[1].amount = Some(0.0625);
+        let yaml = small.to_yaml()?;
+        assert!(yaml.contains("amount: 0.0004\n") && yaml.contains("amount: 0.0625\n"));
+        let amounts = |recipe: &Recipe| {
+            recipe
+                .ingredients
+                .iter()
+                .map(|ingredient| ingredient.amount)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(amounts(&Recipe::from_yaml(&yaml)?), amounts(&small));
+        Ok(())
+    }
+
+    #[test]
+    fn test_formats() -> Result<(), SousError> {
+        let yaml = "
+name:
+  en: pancakes
+  de: Pfannkuchen
+author: test author
+servings: 2
+cook_minutes: 5
+tags: [breakfast]
+nutrition:
+  calories: 250
+ingredients:
+  - name: flour
+    amount: 1.5
+    unit: cup
+    recipe: flour-blend.yml
+  - name: salt
+steps:
+  - mix
+  - en: fry
+    de: braten
+";
+        let recipe = Recipe::from_yaml(yaml)?;
+
+        for format in [RecipeFormat::Yaml, RecipeFormat::Json, RecipeFormat::Toml] {
+            let written = recipe.to_format(format)?;
+            assert_eq!(Recipe::from_format(&written, format)?, recipe, "{format}");
+        }
+
+        let json = recipe.to_json()?;
+        assert!(json.starts_with("{\n  \"schema_version\": 1,\n  \"name\": {\n"));
+        assert!(json.contains("\"amount\": 1.5,\n"));
+
+        let toml = recipe.to_toml()?;
+        assert!(toml.starts_with("schema_version = 1\n"));
+        assert!(toml.contains("[[ingredients]]\nname = \"flour\"\namount = 1.5\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_yaml_stream() -> Result<(), SousError> {
+        let yaml = "
+---
+name: first
+author: test author
+servings: 1
+cook_minutes: 1
+ingredients: []
+steps: []
+---
+schema_version: 1
+name: second
+author: test author
+servings: 2
+cook_minutes: 2
+ingredients: []
+steps: []
+---
+";
+        let recipes = Recipe::from_yaml_stream(yaml)?;
+
+        assert_eq!(recipes.len(), 2);
+        assert_eq!(recipes[0].metadata.name, "first");
+        assert_eq!(recipes[1].metadata.servings, 2);
+        assert!(Recipe::from_yaml(yaml).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_reader() -> Result<(), SousError> {
+        let json = r#"{"name": "test recipe", "author": "test author", "servings": 1,
+            "cook_minutes": 1, "ingredients": [], "steps": ["mix"]}"#;
+        let recipes = Recipe::from_reader(json.as_bytes(), RecipeFormat::Json)?;
+
+        assert_eq!(recipes.len(), 1);
+        assert_eq!(recipes[0].metadata.name, "test recipe");
+        assert_eq!(recipes[0].steps, vec![Text::from("mix")]);
+
+        let yaml = "name: a\nauthor: b\nservings: 1\ncook_minutes: 1\ningredients: []\nsteps: []\n";
+        let recipes =
+            Recipe::from_reader([yaml, yaml].join("---\n").as_bytes(), RecipeFormat::Yaml)?;
+        assert_eq!(recipes.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_recipe_format() {
+        assert_eq!(
+            RecipeFormat::from_path(Path::new("pie.TOML")),
+            RecipeFormat::Toml
+        );
+        assert_eq!(
+            RecipeFormat::from_path(Path::new("pie.yaml")),
+            RecipeFormat::Yaml
+        );
+        assert_eq!(
+            RecipeFormat::from_path(Path::new("pie")),
+            RecipeFormat::Yaml
+        );
+        assert_eq!("json".parse::<RecipeFormat>().unwrap().extension(), "json");
+        assert!("xml".parse::<RecipeFormat>().is_err());
+    }
+
+    #[test]
+    fn test_format_yaml() -> Result<(), SousError> {
+        let yaml = "
+# Grandma's recipe
+
+steps: [mix]
+servings: 1
+name: test recipe # trailing comment
+author: test author
+cook_minutes: 1
+url: null
+tags: []
+source: cookbook page 12
+ingredients:
+  - {name: flour, amount: 1.50, unit: tablespoons, note: sifted}
+";
+
+        let formatted = Recipe::format_yaml(yaml)?;
+        assert_eq!(
+            formatted,
+            "# Grandma's recipe\n\nschema_version: 1\nname: test recipe\nauthor: test author\nservings: 1\n\
+             cook_minutes: 1\nsource: cookbook page 12\ningredients:\n- name: flour\n\
+             \x20 amount: 1.5\n  unit: tbsp\n  note: sifted\nsteps:\n- mix\n"
+        );
+        assert_eq!(Recipe::format_yaml(&formatted)?, formatted);
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_yaml_stream() -> Result<(), SousError> {
+        let yaml = "---\n# first\nname: a\nauthor: b\nservings: 1\ncook_minutes: 1\n\
+                    ingredients: []\nsteps: []\n---\nsteps: []\nname: c\nauthor: d\nservings: 2\n\
+                    cook_minutes: 2\ningredients: []\n--- # end\n# nothing here\n";
+
+        let formatted = Recipe::format_yaml(yaml)?;
+        assert_eq!(
+            formatted,
+            "---\n# first\nschema_version: 1\nname: a\nauthor: b\nservings: 1\ncook_minutes: 1\n\
+             ingredients: []\nsteps: []\n---\nschema_version: 1\nname: c\nauthor: d\nservings: 2\n\
+             cook_minutes: 2\ningredients: []\nsteps: []\n---\n# nothing here\n"
+        );
+        assert_eq!(Recipe::format_yaml(&formatted)?, formatted);
+
+        let versions: Vec<Value> = RecipeFormat::Yaml
+            .parse_all(yaml)?
+            .into_iter()
+            .map(|document| document["name"].clone())
+            .collect();
+        assert_eq!(versions, vec![Value::from("a"), Value::from("c")]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_extra_fields() -> Result<(), SousError> {
+        let yaml = "
+name: test recipe
+author: test author
+servings: 1
+cook_minutes: 1
+rating: 4.5
+notes: [double the butter]
+steps: []
+ingredients: []
+";
+
+        let recipe = Recipe::from_yaml(yaml)?;
+        assert_eq!(recipe.metadata.extra["rating"], Value::from(4.5));
+        assert_eq!(recipe.metadata.extra.len(), 2);
+        assert!(recipe
+            .to_yaml()?
+            .contains("notes:\n- double the butter\nrating: 4.5\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let schema: serde_json::Value = serde_json::from_str(&Recipe::json_schema()).unwrap();
+
+        assert_eq!(schema["title"], "Recipe");
+        assert_eq!(schema["additionalProperties"], true);
+        assert_eq!(schema["properties"]["schema_version"]["maximum"], 1.0);
+        assert!(schema["properties"]["cuisine"].is_object());
+        assert!(schema["definitions"]["Ingredient"]["properties"]["unit"].is_object());
+        assert_eq!(
+            schema["required"],
+            serde_json::json!([
+                "author",
+                "cook_minutes",
+                "ingredients",
+                "name",
+                "servings",
+                "steps"
+            ])
+        );
+    }
+
+    #[test]
+    fn test_published_schema() {
+        assert_eq!(
+            include_str!("../schema/recipe.schema.json"),
+            Recipe::json_schema() + "\n",
+            "regenerate with `sous schema --output schema/recipe.schema.json`"
+        );
+    }
+
+    #[test]
+    fn test_scaled() {
+        let recipe = Recipe {
+            metadata: Metadata {
+                servings: 2,
+                ..Default::default()
+            },
+            ingredients: vec![Ingredient {
+                amount: Some(3.0),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let scaled = recipe.scaled(4);
+        assert_eq!(scaled.metadata.servings, 4);
+        assert_eq!(scaled.ingredients[0].amount, Some(6.0));
+    }
+
     #[test]
     #[should_panic]
     fn from_yaml_missing_name() {
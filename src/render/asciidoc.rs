@@ -0,0 +1,248 @@
+//! Renderer for AsciiDoc output.
+
+use std::fmt::Write;
+
+use crate::render::{prepare, Labels, Renderer};
+use crate::{Index, Recipe, SousError, UnitSystem};
+
+/// Renders recipes in AsciiDoc format, e.g. for Antora.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct AsciiDocRenderer {
+    /// Whether to use a document header with an author line and attributes instead of an author
+    /// paragraph.
+    pub front_matter: bool,
+    /// Whether to output meta information.
+    pub skip_meta: bool,
+    /// Whether to output ingredient list.
+    pub skip_ingredients: bool,
+    /// Whether to output the procedure.
+    pub skip_steps: bool,
+    /// Optionally override the serving count when outputting.
+    pub servings: Option<u32>,
+    /// Optionally convert ingredient amounts into a [UnitSystem].
+    pub units: Option<UnitSystem>,
+    /// Optionally render translated text in this language instead of each recipe's default
+    /// language.
+    pub language: Option<String>,
+    /// Text of headings and labels.
+    pub labels: Labels,
+}
+
+impl AsciiDocRenderer {
+    /// Create default render self.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Renderer for AsciiDocRenderer {
+    fn render(&self, recipe: &Recipe) -> Result<String, SousError> {
+        let mut output = String::new();
+
+        let recipe = prepare(recipe, self.language.as_deref(), self.servings, self.units);
+        let metadata = &recipe.metadata;
+
+        writeln!(output, "= {}", escape(metadata.name.as_str())).unwrap();
+
+        if !self.skip_meta {
+            if self.front_matter {
+                writeln!(output, "{}", attribute(&metadata.author)).unwrap();
+                if let Some(url) = &metadata.url {
+                    writeln!(output, ":url: {}", attribute(url)).unwrap();
+                }
+                if !metadata.tags.is_empty() {
+                    writeln!(output, ":tags: {}", attribute(&metadata.tags.join(", "))).unwrap();
+                }
+                if let Some(cuisine) = &metadata.cuisine {
+                    writeln!(output, ":cuisine: {}", attribute(cuisine)).unwrap();
+                }
+                if let Some(course) = &metadata.course {
+                    writeln!(output, ":course: {}", attribute(course)).unwrap();
+                }
+                if !metadata.dietary.is_empty() {
+                    writeln!(
+                        output,
+                        ":dietary: {}",
+                        attribute(&metadata.dietary.join(", "))
+                    )
+                    .unwrap();
+                }
+                output.push('\n');
+            } else {
+                write!(output, "\n*{}", escape(&metadata.author)).unwrap();
+                if let Some(url) = &metadata.url {
+                    write!(output, " | {}", url).unwrap();
+                }
+                output.push_str("*\n\n");
+            }
+
+            let labels = &self.labels;
+            write!(
+                output,
+                "*{} {}",
+                metadata.servings,
+                escape(&labels.servings)
+            )
+            .unwrap();
+            if let Some(prep) = &metadata.prep_minutes {
+                write!(output, " | {} {}", prep, escape(&labels.prep)).unwrap();
+            }
+            writeln!(
+                output,
+                " | {} {}*",
+                metadata.cook_minutes,
+                escape(&labels.cook)
+            )
+            .unwrap();
+        }
+
+        if !self.skip_ingredients {
+            writeln!(output, "\n== {}\n", escape(&self.labels.ingredients)).unwrap();
+            for ingredient in recipe.ingredients.iter() {
+                writeln!(output, "* {}", escape(&ingredient.to_string())).unwrap();
+            }
+        }
+
+        if !self.skip_steps {
+            writeln!(output, "\n== {}\n", escape(&self.labels.method)).unwrap();
+            for step in recipe.steps.iter() {
+                writeln!(output, ". {}", escape(step.as_str())).unwrap();
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn extension(&self) -> &str {
+        "adoc"
+    }
+
+    fn render_index(&self, index: &Index) -> Result<Option<String>, SousError> {
+        let mut output = String::new();
+
+        writeln!(
+            output,
+            "= {}",
+            escape(index.title.as_ref().unwrap_or(&self.labels.index))
+        )
+        .unwrap();
+        if let Some(author) = &index.author {
+            writeln!(output, "\n*{}*", escape(author)).unwrap();
+        }
+        if let Some(description) = &index.description {
+            writeln!(output, "\n{}", escape(description)).unwrap();
+        }
+
+        for section in index.sections.iter() {
+            writeln!(output, "\n== {}\n", escape(&section.title)).unwrap();
+            for entry in section.entries.iter() {
+                writeln!(
+                    output,
+                    "* xref:{}[{}] | {} {} | {} {}",
+                    entry.link,
+                    escape(&entry.name),
+                    self.servings.unwrap_or(entry.servings),
+                    escape(&self.labels.servings),
+                    entry.total_minutes,
+                    escape(&self.labels.minutes)
+                )
+                .unwrap();
+            }
+        }
+
+        Ok(Some(output))
+    }
+}
+
+/// Escape characters that start inline formatting, macros or attribute references in text by
+/// replacing them with character references.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '*' | '_' | '`' | '#' | '^' | '~' | '+' | '[' | ']' | '{' | '}' | '<' | '>' | '\\' => {
+                write!(escaped, "&#{};", c as u32).unwrap()
+            }
+            '\n' => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Escape an attribute value, keeping it on one line and preventing attribute references.
+fn attribute(text: &str) -> String {
+    let value = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let value = value.trim_end_matches('\\').trim_end();
+
+    value.replace('{', "\\{")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::tests::{gen_index, gen_recipe};
+
+    #[test]
+    fn test_adoc_render() {
+        let recipe = gen_recipe();
+
+        let renderer = AsciiDocRenderer::new();
+        let adoc = renderer.render(&recipe).unwrap();
+
+        assert!(adoc
+            .starts_with("= test recipe\n\n*test author*\n\n*1 servings | 1 minutes cook time*\n"));
+        assert!(adoc.contains("== Ingredients\n\n* 1 test ingredient\n"));
+        assert!(adoc.contains("== Method\n\n. Step one\n"));
+    }
+
+    #[test]
+    fn test_adoc_render_front_matter() {
+        let mut recipe = gen_recipe();
+        recipe.metadata.course = Some("main".to_string());
+
+        let renderer = AsciiDocRenderer {
+            front_matter: true,
+            skip_ingredients: true,
+            servings: Some(2),
+            ..Default::default()
+        };
+        let adoc = renderer.render(&recipe).unwrap();
+
+        assert!(adoc.starts_with("= test recipe\ntest author\n:course: main\n\n*2 servings"));
+        assert!(!adoc.contains("Ingredients"));
+    }
+
+    #[test]
+    fn test_adoc_escape() {
+        assert_eq!(escape("*bold* a_b"), "&#42;bold&#42; a&#95;b");
+        assert_eq!(
+            escape("see [1]\nor {x}"),
+            "see &#91;1&#93; or &#123;x&#125;"
+        );
+        assert_eq!(attribute("a {b}\nc \\"), "a \\{b} c");
+
+        let mut recipe = gen_recipe();
+        recipe.metadata.course = Some("{main}".to_string());
+        recipe.steps[0] = "Use *all* of it".into();
+        let renderer = AsciiDocRenderer {
+            front_matter: true,
+            ..Default::default()
+        };
+        let adoc = renderer.render(&recipe).unwrap();
+
+        assert!(adoc.contains(":course: \\{main}\n"));
+        assert!(adoc.contains(". Use &#42;all&#42; of it\n"));
+    }
+
+    #[test]
+    fn test_adoc_render_index() {
+        let renderer = AsciiDocRenderer::new();
+        let adoc = renderer.render_index(&gen_index()).unwrap().unwrap();
+
+        assert!(adoc.starts_with("= test cookbook\n"));
+        assert!(adoc
+            .contains("== test section\n\n* xref:test.md[test recipe] | 1 servings | 1 minutes"));
+    }
+}
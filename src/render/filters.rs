@@ -0,0 +1,274 @@
+//! Tera filters available to every template.
+//!
+//! The filters share their formatting logic with [MarkdownRenderer](crate::MarkdownRenderer), so
+//! templates can produce the same output without reimplementing it:
+//!
+//! * `scale(factor)` multiplies a number, or the amount of an ingredient.
+//! * `fraction` formats an amount with common fractions, e.g. "1 1/2".
+//! * `convert(to)` converts an ingredient into a unit system ("metric" or "imperial") or a unit.
+//!   Numbers may be converted by also passing the unit they are given in with `from`.
+//! * `duration` formats minutes as hours and minutes, e.g. "1 h 15 min".
+//! * `slugify` turns text into a URL-safe identifier.
+//! * `pluralize_unit(amount)` pluralizes a unit written out as a word, e.g. "cups".
+//! * `ingredient` formats an ingredient as a line of a recipe, e.g. "1 1/2 cups flour".
+//! * `number` formats a decimal number, e.g. "0,15" in German.
+//!
+//! Filters that format numbers or units use the [Locale] the environment was registered with, which
+//! may be overridden for a single use with a `locale` argument, e.g. `fraction(locale="de")`.
+
+use std::collections::HashMap;
+
+use tera::{Tera, Value};
+
+use crate::format::{duration, slugify};
+use crate::units::convert_unit;
+use crate::{Ingredient, Locale, SousError, UnitSystem};
+
+/// Register every sous filter with the environment, formatting for the given [Locale].
+///
+/// Registering again replaces the filters, changing the locale of an existing environment.
+pub(crate) fn register(env: &mut Tera, locale: Locale) {
+    env.register_filter("scale", scale_filter);
+    env.register_filter("fraction", move |value: &Value, args: &Args| {
+        fraction_filter(value, args, locale)
+    });
+    env.register_filter("convert", convert_filter);
+    env.register_filter("duration", duration_filter);
+    env.register_filter("slugify", slugify_filter);
+    env.register_filter("pluralize_unit", move |value: &Value, args: &Args| {
+        pluralize_unit_filter(value, args, locale)
+    });
+    env.register_filter("ingredient", move |value: &Value, args: &Args| {
+        ingredient_filter(value, args, locale)
+    });
+    env.register_filter("number", move |value: &Value, args: &Args| {
+        number_filter(value, args, locale)
+    });
+}
+
+type Args = HashMap<String, Value>;
+
+fn scale_filter(value: &Value, args: &Args) -> tera::Result<Value> {
+    let factor = number_arg(args, "scale", "factor")?;
+
+    match value {
+        Value::Number(_) => Ok(Value::from(number(value, "scale")? * factor)),
+        Value::Object(_) => {
+            let mut ingredient = ingredient(value, "scale")?;
+            ingredient.amount = ingredient.amount.map(|amount| amount * factor);
+            Ok(tera::to_value(ingredient)?)
+        }
+        _ => Err(tera::Error::msg("scale expects a number or an ingredient")),
+    }
+}
+
+fn fraction_filter(value: &Value, args: &Args, locale: Locale) -> tera::Result<Value> {
+    let locale = locale_arg(args, "fraction", locale)?;
+
+    match value {
+        Value::Null => Ok(Value::String(String::new())),
+        _ => Ok(Value::String(locale.fraction(number(value, "fraction")?))),
+    }
+}
+
+fn number_filter(value: &Value, args: &Args, locale: Locale) -> tera::Result<Value> {
+    let locale = locale_arg(args, "number", locale)?;
+
+    Ok(Value::String(locale.number(number(value, "number")?)))
+}
+
+fn convert_filter(value: &Value, args: &Args) -> tera::Result<Value> {
+    let to = string_arg(args, "convert", "to")?;
+
+    if let Value::Number(_) = value {
+        let amount = number(value, "convert")?;
+        let from = string_arg(args, "convert", "from")?;
+        return match convert_unit(amount, from, to) {
+            Some((amount, _)) => Ok(Value::from(amount)),
+            None => Err(tera::Error::msg(format!(
+                "convert cannot convert from {from} to {to}"
+            ))),
+        };
+    }
+
+    let ingredient = ingredient(value, "convert")?;
+    let converted = match to.parse::<UnitSystem>() {
+        Ok(system) => ingredient.to_system(system),
+        Err(_) => match (ingredient.amount, &ingredient.unit) {
+            (Some(amount), Some(unit)) => match convert_unit(amount, unit, to) {
+                Some((amount, unit)) => Ingredient {
+                    amount: Some(amount),
+                    unit: Some(unit.to_string()),
+                    ..ingredient
+                },
+                None => ingredient,
+            },
+            _ => ingredient,
+        },
+    };
+
+    Ok(tera::to_value(converted)?)
+}
+
+fn duration_filter(value: &Value, _: &Args) -> tera::Result<Value> {
+    match value.as_u64() {
+        Some(minutes) => Ok(Value::String(duration(minutes as u32))),
+        None => Err(tera::Error::msg("duration expects a number of minutes")),
+    }
+}
+
+fn slugify_filter(value: &Value, _: &Args) -> tera::Result<Value> {
+    match value.as_str() {
+        Some(text) => Ok(Value::String(slugify(text))),
+        None => Err(tera::Error::msg("slugify expects a string")),
+    }
+}
+
+fn pluralize_unit_filter(value: &Value, args: &Args, locale: Locale) -> tera::Result<Value> {
+    let locale = locale_arg(args, "pluralize_unit", locale)?;
+    let amount = match args.get("amount") {
+        None | Some(Value::Null) => 1.0,
+        Some(_) => number_arg(args, "pluralize_unit", "amount")?,
+    };
+
+    match value.as_str() {
+        Some(unit) => Ok(Value::String(locale.pluralize_unit(unit, amount))),
+        None => Err(tera::Error::msg("pluralize_unit expects a string")),
+    }
+}
+
+fn ingredient_filter(value: &Value, args: &Args, locale: Locale) -> tera::Result<Value> {
+    let locale = locale_arg(args, "ingredient", locale)?;
+
+    Ok(Value::String(
+        locale.ingredient(&ingredient(value, "ingredient")?),
+    ))
+}
+
+fn number(value: &Value, filter: &str) -> tera::Result<f32> {
+    match value.as_f64() {
+        Some(number) => Ok(number as f32),
+        None => Err(tera::Error::msg(format!("{filter} expects a number"))),
+    }
+}
+
+fn number_arg(args: &Args, filter: &str, name: &str) -> tera::Result<f32> {
+    match args.get(name).and_then(Value::as_f64) {
+        Some(number) => Ok(number as f32),
+        None => Err(tera::Error::msg(format!(
+            "{filter} expects a number argument `{name}`"
+        ))),
+    }
+}
+
+fn string_arg<'a>(args: &'a Args, filter: &str, name: &str) -> tera::Result<&'a str> {
+    match args.get(name).and_then(Value::as_str) {
+        Some(string) => Ok(string),
+        None => Err(tera::Error::msg(format!(
+            "{filter} expects a string argument `{name}`"
+        ))),
+    }
+}
+
+fn locale_arg(args: &Args, filter: &str, default: Locale) -> tera::Result<Locale> {
+    match args.get("locale") {
+        None | Some(Value::Null) => Ok(default),
+        Some(_) => string_arg(args, filter, "locale")?
+            .parse()
+            .map_err(|e: SousError| tera::Error::msg(format!("{filter}: {e}"))),
+    }
+}
+
+fn ingredient(value: &Value, filter: &str) -> tera::Result<Ingredient> {
+    tera::from_value(value.clone())
+        .map_err(|_| tera::Error::msg(format!("{filter} expects an ingredient")))
+}
+
+#[cfg(test)]
+mod tests {
+    use tera::Context;
+
+    use super::*;
+
+    fn render(template: &str) -> String {
+        let mut env = Tera::default();
+        register(&mut env, Locale::default());
+
+        let mut ctx = Context::new();
+        ctx.insert(
+            "ingredient",
+            &Ingredient {
+                name: "milk".into(),
+                amount: Some(1.5),
+                unit: Some("cup".to_string()),
+                ..Default::default()
+            },
+        );
+
+        env.render_str(template, &ctx).unwrap()
+    }
+
+    #[test]
+    fn test_scale() {
+        assert_eq!(render("{{ 3 | scale(factor=0.5) }}"), "1.5");
+        assert_eq!(
+            render("{{ ingredient | scale(factor=2) | ingredient }}"),
+            "3 cups milk"
+        );
+    }
+
+    #[test]
+    fn test_fraction() {
+        assert_eq!(render("{{ ingredient.amount | fraction }}"), "1 1/2");
+    }
+
+    #[test]
+    fn test_convert() {
+        assert_eq!(
+            render("{{ ingredient | convert(to=\"metric\") | ingredient }}"),
+            "355 ml milk"
+        );
+        assert_eq!(
+            render("{{ ingredient | convert(to=\"tbsp\") | ingredient }}"),
+            "24 tbsp milk"
+        );
+        assert_eq!(render("{{ 1000 | convert(from=\"g\", to=\"kg\") }}"), "1");
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(render("{{ 75 | duration }}"), "1 h 15 min");
+    }
+
+    #[test]
+    fn test_pluralize_unit() {
+        assert_eq!(
+            render("{{ ingredient.unit | pluralize_unit(amount=ingredient.amount) }}"),
+            "cups"
+        );
+        assert_eq!(render("{{ \"cup\" | pluralize_unit }}"), "cup");
+    }
+
+    #[test]
+    fn test_locale() {
+        assert_eq!(render("{{ 0.15 | number }}"), "0.15");
+        assert_eq!(render("{{ 0.15 | number(locale=\"de\") }}"), "0,15");
+        assert_eq!(
+            render("{{ ingredient | scale(factor=1.1) | ingredient(locale=\"fr\") }}"),
+            "1,65 cup milk"
+        );
+
+        let mut env = Tera::default();
+        register(&mut env, Locale::German);
+        assert_eq!(
+            env.render_str("{{ 0.15 | fraction }}", &tera::Context::new())
+                .unwrap(),
+            "0,15"
+        );
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(render("{{ \"Quick Meals\" | slugify }}"), "quick-meals");
+    }
+}
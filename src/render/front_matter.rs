@@ -0,0 +1,313 @@
+//! Front matter describing a recipe's [Metadata] for static site generators.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde_yaml::{Mapping, Value};
+
+use crate::{Metadata, SousError};
+
+/// Static site generator whose conventions decide the keys of rendered front matter.
+///
+/// | Metadata        | Sous       | Hugo              | Jekyll     | Zola              |
+/// | --------------- | ---------- | ----------------- | ---------- | ----------------- |
+/// | `name`          | `title`    | `title`           | `title`    | `title`           |
+/// | `author`        | `author`   | `params.author`   | `author`   | `authors` (list)  |
+/// | `url`           | `url`      | `params.source`   | `source`   | `extra.source`    |
+/// | `image`         | `image`    | `images` (list)   | `image`    | `extra.image`     |
+/// | `tags`          | `tags`     | `tags`            | `tags`     | `taxonomies.tags` |
+/// | `language`      | `language` | `params.language` | `lang`     | `extra.language`  |
+/// | everything else | field name | `params.<field>`  | field name | `extra.<field>`   |
+///
+/// "Everything else" covers servings, times (including `total_minutes`), cuisine, course, dietary
+/// labels and [Metadata::extra] fields, which never replace a key listed above. The recipe's URL
+/// is never written as `url`, which Hugo and Jekyll use for the address of the rendered page, and
+/// Zola is limited to the top-level keys it knows about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum FrontMatterStyle {
+    /// Metadata field names, with the recipe name as `title`.
+    #[default]
+    Sous,
+    /// Keys understood by Hugo, with custom fields under `params`.
+    Hugo,
+    /// Keys understood by Jekyll and its SEO plugin.
+    Jekyll,
+    /// Keys understood by Zola, with custom fields under `extra`.
+    Zola,
+}
+
+impl fmt::Display for FrontMatterStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrontMatterStyle::Sous => write!(f, "sous"),
+            FrontMatterStyle::Hugo => write!(f, "hugo"),
+            FrontMatterStyle::Jekyll => write!(f, "jekyll"),
+            FrontMatterStyle::Zola => write!(f, "zola"),
+        }
+    }
+}
+
+impl FromStr for FrontMatterStyle {
+    type Err = SousError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sous" => Ok(FrontMatterStyle::Sous),
+            "hugo" => Ok(FrontMatterStyle::Hugo),
+            "jekyll" => Ok(FrontMatterStyle::Jekyll),
+            "zola" => Ok(FrontMatterStyle::Zola),
+            _ => Err(SousError::UnknownFrontMatterStyle(s.to_string())),
+        }
+    }
+}
+
+/// Serialization format of rendered front matter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum FrontMatterFormat {
+    /// YAML delimited by `---` lines.
+    #[default]
+    Yaml,
+    /// TOML delimited by `+++` lines, as read by Hugo and Zola.
+    Toml,
+}
+
+impl fmt::Display for FrontMatterFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrontMatterFormat::Yaml => write!(f, "yaml"),
+            FrontMatterFormat::Toml => write!(f, "toml"),
+        }
+    }
+}
+
+impl FromStr for FrontMatterFormat {
+    type Err = SousError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "yaml" | "yml" => Ok(FrontMatterFormat::Yaml),
+            "toml" => Ok(FrontMatterFormat::Toml),
+            _ => Err(SousError::UnknownFrontMatterFormat(s.to_string())),
+        }
+    }
+}
+
+/// Serialize `metadata` as delimited front matter, with keys following `style`.
+///
+/// Fields that are unset or empty are left out.
+pub fn front_matter(
+    metadata: &Metadata,
+    style: FrontMatterStyle,
+    format: FrontMatterFormat,
+) -> Result<String, SousError> {
+    let mut root = Mapping::new();
+    let mut nested = Mapping::new();
+
+    let title = Value::from(metadata.name.as_str());
+    let author = Value::from(metadata.author.as_str());
+    let url = optional(metadata.url.clone());
+    let image = optional(metadata.image.clone());
+    let tags = Value::from(metadata.tags.clone());
+    let language = optional(metadata.language.clone());
+
+    insert(&mut root, "title", title);
+    match style {
+        FrontMatterStyle::Sous => {
+            insert(&mut root, "author", author);
+            insert(&mut root, "url", url);
+            insert(&mut root, "image", image);
+        }
+        FrontMatterStyle::Hugo => {
+            insert(&mut root, "images", Value::from(metadata.image.as_slice()));
+            insert(&mut root, "tags", tags.clone());
+            insert(&mut nested, "author", author);
+            insert(&mut nested, "source", url);
+        }
+        FrontMatterStyle::Jekyll => {
+            insert(&mut root, "author", author);
+            insert(&mut root, "source", url);
+            insert(&mut root, "image", image);
+        }
+        FrontMatterStyle::Zola => {
+            insert(&mut root, "authors", Value::from(vec![author]));
+            insert(&mut nested, "source", url);
+            insert(&mut nested, "image", image);
+        }
+    }
+
+    let fields = match style {
+        FrontMatterStyle::Sous | FrontMatterStyle::Jekyll => &mut root,
+        FrontMatterStyle::Hugo | FrontMatterStyle::Zola => &mut nested,
+    };
+    insert(fields, "servings", Value::from(metadata.servings));
+    insert(fields, "prep_minutes", optional(metadata.prep_minutes));
+    insert(fields, "cook_minutes", Value::from(metadata.cook_minutes));
+    insert(
+        fields,
+        "total_minutes",
+        Value::from(metadata.total_minutes()),
+    );
+    if matches!(style, FrontMatterStyle::Sous | FrontMatterStyle::Jekyll) {
+        insert(fields, "tags", tags.clone());
+    }
+    insert(fields, "cuisine", optional(metadata.cuisine.clone()));
+    insert(fields, "course", optional(metadata.course.clone()));
+    insert(fields, "dietary", Value::from(metadata.dietary.clone()));
+    let language_key = match style {
+        FrontMatterStyle::Jekyll => "lang",
+        _ => "language",
+    };
+    insert(fields, language_key, language);
+    for (key, value) in metadata.extra.iter() {
+        if !fields.contains_key(key.as_str()) {
+            insert(fields, key, value.clone());
+        }
+    }
+
+    match style {
+        FrontMatterStyle::Sous | FrontMatterStyle::Jekyll => {}
+        FrontMatterStyle::Hugo => insert(&mut root, "params", Value::from(nested)),
+        FrontMatterStyle::Zola => {
+            let mut taxonomies = Mapping::new();
+            insert(&mut taxonomies, "tags", tags);
+            insert(&mut root, "taxonomies", Value::from(taxonomies));
+            insert(&mut root, "extra", Value::from(nested));
+        }
+    }
+
+    Ok(match format {
+        FrontMatterFormat::Yaml => format!("---\n{}---\n", serde_yaml::to_string(&root)?),
+        FrontMatterFormat::Toml => format!("+++\n{}+++\n", toml::to_string(&root)?),
+    })
+}
+
+/// Convert an optional field into a [Value], using null for [None].
+fn optional<T: Into<Value>>(value: Option<T>) -> Value {
+    value.map_or(Value::Null, Into::into)
+}
+
+/// Insert `value` under `key`, skipping null values and empty lists and maps.
+fn insert(map: &mut Mapping, key: &str, value: Value) {
+    let empty = match &value {
+        Value::Null => true,
+        Value::Sequence(values) => values.is_empty(),
+        Value::Mapping(values) => values.is_empty(),
+        _ => false,
+    };
+    if !empty {
+        map.insert(Value::from(key), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gen_metadata() -> Metadata {
+        Metadata {
+            name: "Pancakes: fluffy".into(),
+            author: "test author".to_string(),
+            servings: 4,
+            url: Some("https://example.com/pancakes".to_string()),
+            image: Some("pancakes.jpg".to_string()),
+            prep_minutes: Some(5),
+            cook_minutes: 10,
+            tags: vec!["quick".to_string()],
+            course: Some("breakfast".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_front_matter_sous() {
+        let yaml = front_matter(
+            &gen_metadata(),
+            FrontMatterStyle::Sous,
+            FrontMatterFormat::Yaml,
+        )
+        .unwrap();
+
+        assert_eq!(
+            yaml,
+            "---\ntitle: 'Pancakes: fluffy'\nauthor: test author\n\
+             url: https://example.com/pancakes\nimage: pancakes.jpg\nservings: 4\n\
+             prep_minutes: 5\ncook_minutes: 10\ntotal_minutes: 15\ntags:\n- quick\n\
+             course: breakfast\n---\n"
+        );
+    }
+
+    #[test]
+    fn test_front_matter_hugo() {
+        let toml = front_matter(
+            &gen_metadata(),
+            FrontMatterStyle::Hugo,
+            FrontMatterFormat::Toml,
+        )
+        .unwrap();
+
+        assert!(toml.starts_with("+++\ntitle = \"Pancakes: fluffy\"\n"));
+        assert!(toml.contains("images = [\"pancakes.jpg\"]\ntags = [\"quick\"]\n"));
+        assert!(toml.contains("\n[params]\nauthor = \"test author\"\n"));
+        assert!(toml.contains("source = \"https://example.com/pancakes\"\n"));
+        assert!(toml.ends_with("course = \"breakfast\"\n+++\n"));
+        assert!(!toml.contains("url ="));
+    }
+
+    #[test]
+    fn test_front_matter_jekyll() {
+        let mut metadata = gen_metadata();
+        metadata.language = Some("de".to_string());
+
+        let yaml =
+            front_matter(&metadata, FrontMatterStyle::Jekyll, FrontMatterFormat::Yaml).unwrap();
+
+        assert!(yaml.contains("\nsource: https://example.com/pancakes\nimage: pancakes.jpg\n"));
+        assert!(yaml.ends_with("lang: de\n---\n"));
+    }
+
+    #[test]
+    fn test_front_matter_extra() {
+        let mut metadata = gen_metadata();
+        metadata.extra.insert("rating".to_string(), Value::from(5));
+        metadata
+            .extra
+            .insert("title".to_string(), Value::from("ignored"));
+
+        let yaml =
+            front_matter(&metadata, FrontMatterStyle::Sous, FrontMatterFormat::Yaml).unwrap();
+        assert!(yaml.starts_with("---\ntitle: 'Pancakes: fluffy'\n"));
+        assert!(yaml.ends_with("course: breakfast\nrating: 5\n---\n"));
+
+        let toml =
+            front_matter(&metadata, FrontMatterStyle::Zola, FrontMatterFormat::Toml).unwrap();
+        assert!(toml.ends_with("rating = 5\ntitle = \"ignored\"\n+++\n"));
+    }
+
+    #[test]
+    fn test_front_matter_zola() {
+        let toml = front_matter(
+            &gen_metadata(),
+            FrontMatterStyle::Zola,
+            FrontMatterFormat::Toml,
+        )
+        .unwrap();
+
+        assert!(toml.contains("authors = [\"test author\"]\n"));
+        assert!(toml.contains("\n[taxonomies]\ntags = [\"quick\"]\n"));
+        assert!(toml.contains("\n[extra]\nsource = \"https://example.com/pancakes\"\n"));
+        assert!(toml.contains("servings = 4\n"));
+    }
+
+    #[test]
+    fn test_front_matter_style_from_str() {
+        assert_eq!(
+            "Hugo".parse::<FrontMatterStyle>().unwrap(),
+            FrontMatterStyle::Hugo
+        );
+        assert!("gatsby".parse::<FrontMatterStyle>().is_err());
+        assert_eq!(
+            "yml".parse::<FrontMatterFormat>().unwrap(),
+            FrontMatterFormat::Yaml
+        );
+    }
+}
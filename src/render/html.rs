@@ -0,0 +1,204 @@
+//! Renderer for HTML output.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use tera::{Context, Tera};
+
+use crate::render::{filters, prepare, theme, Labels, Renderer};
+use crate::{Index, IndexEntry, IndexSection, Locale, Recipe, SousError, UnitSystem};
+
+/// Renders recipes as HTML pages using a themeable set of [Tera] templates.
+///
+/// The built-in theme consists of `base.html`, `recipe.html`, `index.html`, `tag.html` and
+/// `style.css`. Any of these may be replaced with [HtmlRenderer::with_theme].
+#[derive(Clone, Debug)]
+pub struct HtmlRenderer {
+    env: Tera,
+    assets: Vec<(String, PathBuf)>,
+    locale: Locale,
+    /// Title of the site, shown in every page header.
+    pub title: Option<String>,
+    /// Optionally override the serving count when outputting.
+    pub servings: Option<u32>,
+    /// Optionally convert ingredient amounts into a [UnitSystem].
+    pub units: Option<UnitSystem>,
+    /// Optionally render translated text in this language instead of each recipe's default
+    /// language.
+    pub language: Option<String>,
+}
+
+impl HtmlRenderer {
+    /// Create a renderer using the built-in theme.
+    pub fn new() -> Result<Self, SousError> {
+        let mut env = theme::environment();
+        env.add_raw_templates(theme::HTML_THEME.to_vec())?;
+
+        Ok(HtmlRenderer {
+            env,
+            assets: Vec::new(),
+            locale: Locale::default(),
+            language: None,
+            title: None,
+            servings: None,
+            units: None,
+        })
+    }
+
+    /// Override built-in templates with those found in the provided theme directory.
+    ///
+    /// Files are matched to built-in templates by name, so a theme may replace as few or as many
+    /// templates as desired. Additional files may be used as layouts or partials. Files other
+    /// than `.html`, `.md` and `.tera` templates are static assets, such as a replacement
+    /// `style.css`, which are copied into generated sites as they are.
+    pub fn with_theme(mut self, dir: &Path) -> Result<Self, SousError> {
+        let assets = theme::add_dir(&mut self.env, dir)?;
+        self.assets.extend(assets);
+        Ok(self)
+    }
+
+    /// Static assets of the theme, as pairs of path relative to the output and source file.
+    pub fn assets(&self) -> &[(String, PathBuf)] {
+        &self.assets
+    }
+
+    /// Localize labels and numbers into the provided [Locale].
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        filters::register(&mut self.env, locale);
+        self.locale = locale;
+        self
+    }
+
+    /// Labels in the renderer's [Locale].
+    pub fn labels(&self) -> Labels {
+        self.locale.labels()
+    }
+
+    /// Construct the site stylesheet.
+    pub fn render_stylesheet(&self) -> Result<String, SousError> {
+        Ok(self.env.render("style.css", &Context::new())?)
+    }
+
+    /// Apply the serving count override to index entries.
+    fn entries(&self, entries: &[IndexEntry]) -> Vec<IndexEntry> {
+        entries
+            .iter()
+            .map(|entry| IndexEntry {
+                servings: self.servings.unwrap_or(entry.servings),
+                ..entry.clone()
+            })
+            .collect()
+    }
+
+    fn context(&self, root: &str) -> Context {
+        let labels = self.labels();
+        let mut ctx = Context::new();
+        ctx.insert("locale", &self.locale);
+        ctx.insert("root", root);
+        ctx.insert("site_title", self.title.as_ref().unwrap_or(&labels.recipes));
+        ctx.insert("labels", &labels);
+        ctx
+    }
+}
+
+impl Renderer for HtmlRenderer {
+    fn render(&self, recipe: &Recipe) -> Result<String, SousError> {
+        let recipe = prepare(recipe, self.language.as_deref(), self.servings, self.units);
+
+        let mut ctx = self.context("");
+        ctx.extend(theme::recipe_context(&recipe)?);
+
+        Ok(self.env.render("recipe.html", &ctx)?)
+    }
+
+    fn extension(&self) -> &str {
+        "html"
+    }
+
+    fn render_index(&self, index: &Index) -> Result<Option<String>, SousError> {
+        let tags: BTreeSet<&String> = index
+            .sections
+            .iter()
+            .flat_map(|section| section.entries.iter())
+            .flat_map(|entry| entry.tags.iter())
+            .collect();
+
+        let mut ctx = self.context("");
+        ctx.extend(Context::from_serialize(index)?);
+        ctx.insert("tags", &tags);
+        if let (None, Some(title)) = (&self.title, &index.title) {
+            ctx.insert("site_title", title);
+        }
+        let sections: Vec<IndexSection> = index
+            .sections
+            .iter()
+            .map(|section| IndexSection {
+                title: section.title.clone(),
+                entries: self.entries(&section.entries),
+            })
+            .collect();
+        ctx.insert("sections", &sections);
+
+        Ok(Some(self.env.render("index.html", &ctx)?))
+    }
+
+    fn render_tag(&self, tag: &IndexSection) -> Result<Option<String>, SousError> {
+        let mut ctx = self.context("../");
+        ctx.insert("title", &tag.title);
+        ctx.insert("entries", &self.entries(&tag.entries));
+
+        Ok(Some(self.env.render("tag.html", &ctx)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::tests::{gen_index, gen_recipe};
+
+    #[test]
+    fn test_html_render() {
+        let mut recipe = gen_recipe();
+        recipe.metadata.tags = vec!["Quick Meals".to_string()];
+        recipe.ingredients[0].recipe = Some("sub-recipe.yml".to_string());
+
+        let renderer = HtmlRenderer::new().unwrap();
+        let html = renderer.render(&recipe).unwrap();
+
+        assert!(html.contains("<title>test recipe | Recipes</title>"));
+        assert!(html.contains("<a href=\"tags/quick-meals.html\">Quick Meals</a>"));
+        assert!(html.contains("<a href=\"sub-recipe.html\">test ingredient</a>"));
+        assert!(html.contains("<li>Step one</li>"));
+    }
+
+    #[test]
+    fn test_html_render_escapes() {
+        let mut recipe = gen_recipe();
+        recipe.metadata.name = "<b>bold</b>".into();
+
+        let renderer = HtmlRenderer::new().unwrap();
+        let html = renderer.render(&recipe).unwrap();
+
+        assert!(html.contains("&lt;b&gt;bold&lt;&#x2F;b&gt;"));
+    }
+
+    #[test]
+    fn test_html_render_index() {
+        let renderer = HtmlRenderer::new().unwrap();
+        let html = renderer.render_index(&gen_index()).unwrap().unwrap();
+
+        assert!(html.contains("<h1>test cookbook</h1>"));
+        assert!(html.contains("<a href=\"test.md\">test recipe</a>"));
+    }
+
+    #[test]
+    fn test_html_render_tag() {
+        let renderer = HtmlRenderer::new().unwrap();
+        let tag = gen_index().sections.remove(0);
+        let html = renderer.render_tag(&tag).unwrap().unwrap();
+
+        assert!(html.contains("<h1>test section</h1>"));
+        assert!(html.contains("<a href=\"../test.md\">test recipe</a>"));
+        assert!(html.contains("href=\"../style.css\""));
+    }
+}
@@ -0,0 +1,317 @@
+//! Renderer for LaTeX output.
+
+use std::fmt::Write;
+
+use crate::format::duration;
+use crate::render::{prepare, Labels, Renderer};
+use crate::{Cookbook, Index, Recipe, SousError, UnitSystem};
+
+/// Preamble of a cookbook document, defining the environments used by rendered recipes. Their
+/// headings are `\ingredientsname` and `\methodname`, defined by [book] from [Labels].
+const PREAMBLE: &str = r"\documentclass[11pt]{book}
+\usepackage[utf8]{inputenc}
+\usepackage[T1]{fontenc}
+\usepackage{multicol}
+
+\newenvironment{recipe}[1]{\section*{#1}\addcontentsline{toc}{section}{#1}}{\clearpage}
+\newcommand{\recipemeta}[2]{\noindent\textit{#1}\par\noindent#2\par\medskip}
+\newenvironment{ingredients}{\subsection*{\ingredientsname}\begin{multicols}{2}\begin{itemize}}{\end{itemize}\end{multicols}}
+\newenvironment{method}{\subsection*{\methodname}\begin{enumerate}}{\end{enumerate}}
+";
+
+/// Renders recipes as LaTeX.
+///
+/// Each recipe is rendered as a `recipe` environment, with ingredients set in two columns followed
+/// by the method, both of which may break across pages. Rendered recipes are meant to be included
+/// in a cookbook document, either through the index produced by [Renderer::render_index] or with
+/// [LatexRenderer::render_cookbook], both of which define the environments used.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct LatexRenderer {
+    /// Whether to output meta information.
+    pub skip_meta: bool,
+    /// Whether to output ingredient list.
+    pub skip_ingredients: bool,
+    /// Whether to output the procedure.
+    pub skip_steps: bool,
+    /// Optionally override the serving count when outputting.
+    pub servings: Option<u32>,
+    /// Optionally convert ingredient amounts into a [UnitSystem].
+    pub units: Option<UnitSystem>,
+    /// Optionally render translated text in this language instead of each recipe's default
+    /// language.
+    pub language: Option<String>,
+    /// Text of headings and labels.
+    pub labels: Labels,
+}
+
+impl LatexRenderer {
+    /// Create default render self.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Construct a complete LaTeX book containing every recipe in the provided [Cookbook], with
+    /// one chapter per section of the cookbook's index.
+    pub fn render_cookbook(&self, cookbook: &Cookbook) -> Result<String, SousError> {
+        let index = cookbook.index("tex", self.language.as_deref(), &self.labels)?;
+
+        let mut chapters = Vec::new();
+        for section in index.sections.iter() {
+            let mut body = String::new();
+            for entry in section.entries.iter() {
+                body.push_str(&self.render(&cookbook.load_recipe(&entry.file)?)?);
+            }
+            chapters.push(body);
+        }
+
+        Ok(book(&index, &chapters, &self.labels))
+    }
+}
+
+impl Renderer for LatexRenderer {
+    fn render(&self, recipe: &Recipe) -> Result<String, SousError> {
+        let mut output = String::new();
+
+        let recipe = prepare(recipe, self.language.as_deref(), self.servings, self.units);
+
+        writeln!(
+            output,
+            "\\begin{{recipe}}{{{}}}",
+            escape(recipe.metadata.name.as_str())
+        )
+        .unwrap();
+
+        if !self.skip_meta {
+            let mut author = escape(&recipe.metadata.author);
+            if let Some(url) = &recipe.metadata.url {
+                write!(author, " | \\texttt{{{}}}", escape(url)).unwrap();
+            }
+
+            let mut times = vec![format!("{} servings", recipe.metadata.servings)];
+            if let Some(prep) = recipe.metadata.prep_minutes {
+                times.push(format!("{} prep", duration(prep)));
+            }
+            times.push(format!(
+                "{} cook time",
+                duration(recipe.metadata.cook_minutes)
+            ));
+
+            writeln!(
+                output,
+                "\\recipemeta{{{}}}{{{}}}",
+                author,
+                times.join(" | ")
+            )
+            .unwrap();
+        }
+
+        if !self.skip_ingredients && !recipe.ingredients.is_empty() {
+            output.push_str("\\begin{ingredients}\n");
+            for ingredient in recipe.ingredients.iter() {
+                writeln!(output, "  \\item {}", escape(&ingredient.to_string())).unwrap();
+            }
+            output.push_str("\\end{ingredients}\n");
+        }
+
+        if !self.skip_steps && !recipe.steps.is_empty() {
+            output.push_str("\\begin{method}\n");
+            for step in recipe.steps.iter() {
+                writeln!(output, "  \\item {}", escape(step.as_str())).unwrap();
+            }
+            output.push_str("\\end{method}\n");
+        }
+
+        output.push_str("\\end{recipe}\n\n");
+        Ok(output)
+    }
+
+    fn extension(&self) -> &str {
+        "tex"
+    }
+
+    /// Construct a LaTeX book including the rendered recipe files linked from the index, with one
+    /// chapter per index section.
+    ///
+    /// Returns [SousError::ValidationError] if a linked file name contains `%`, `#`, `{`, `}` or
+    /// `\`, which cannot be included.
+    fn render_index(&self, index: &Index) -> Result<Option<String>, SousError> {
+        let mut chapters = Vec::new();
+        for section in index.sections.iter() {
+            let mut body = String::new();
+            for entry in section.entries.iter() {
+                writeln!(body, "\\input{{{}}}", file_name(&entry.link)?).unwrap();
+            }
+            chapters.push(body);
+        }
+
+        Ok(Some(book(index, &chapters, &self.labels)))
+    }
+}
+
+/// Construct a book document from the index metadata and the body of each of its sections.
+fn book(index: &Index, chapters: &[String], labels: &Labels) -> String {
+    let mut output = String::from(PREAMBLE);
+    writeln!(
+        output,
+        "\\newcommand{{\\ingredientsname}}{{{}}}",
+        escape(&labels.ingredients)
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "\\newcommand{{\\methodname}}{{{}}}",
+        escape(&labels.method)
+    )
+    .unwrap();
+
+    let title = index.title.as_ref().unwrap_or(&labels.recipes);
+    writeln!(output, "\n\\title{{{}}}", escape(title)).unwrap();
+    writeln!(
+        output,
+        "\\author{{{}}}",
+        escape(index.author.as_deref().unwrap_or_default())
+    )
+    .unwrap();
+    output.push_str("\\date{}\n\n\\begin{document}\n\\maketitle\n");
+    if let Some(description) = &index.description {
+        writeln!(output, "\n{}\n", escape(description)).unwrap();
+    }
+    output.push_str("\\tableofcontents\n");
+
+    for (section, body) in index.sections.iter().zip(chapters) {
+        writeln!(output, "\n\\chapter{{{}}}\n", escape(&section.title)).unwrap();
+        output.push_str(body);
+    }
+
+    output.push_str("\\end{document}\n");
+    output
+}
+
+/// Escape characters with special meaning in LaTeX.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Quote a file name for `\\input`, keeping characters such as `_` and `&` literal.
+fn file_name(name: &str) -> Result<String, SousError> {
+    if name.contains(['%', '#', '{', '}', '\\']) {
+        return Err(SousError::ValidationError(vec![format!(
+            "file name cannot be included in LaTeX: {name}"
+        )]));
+    }
+
+    Ok(format!("\\detokenize{{{name}}}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::render::tests::{gen_index, gen_recipe};
+    use crate::tests::temp_dir;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape(r"50% & $5 #1 a_b {x} ~ ^ \"),
+            r"50\% \& \$5 \#1 a\_b \{x\} \textasciitilde{} \textasciicircum{} \textbackslash{}"
+        );
+    }
+
+    #[test]
+    fn test_latex_render() {
+        let recipe = gen_recipe();
+
+        let renderer = LatexRenderer::new();
+        let tex = renderer.render(&recipe).unwrap();
+
+        assert!(tex.starts_with("\\begin{recipe}{test recipe}\n"));
+        assert!(tex.contains("\\recipemeta{test author}{1 servings | 1 min cook time}"));
+        assert!(
+            tex.contains("\\begin{ingredients}\n  \\item 1 test ingredient\n\\end{ingredients}")
+        );
+        assert!(tex.contains("\\begin{method}\n  \\item Step one\n\\end{method}"));
+        assert!(tex.ends_with("\\end{recipe}\n\n"));
+    }
+
+    #[test]
+    fn test_latex_render_skip() {
+        let recipe = gen_recipe();
+
+        let renderer = LatexRenderer {
+            skip_meta: true,
+            skip_steps: true,
+            servings: Some(2),
+            ..Default::default()
+        };
+        let tex = renderer.render(&recipe).unwrap();
+
+        assert!(!tex.contains("\\recipemeta"));
+        assert!(!tex.contains("\\begin{method}"));
+        assert!(tex.contains("\\item 2 test ingredient"));
+    }
+
+    #[test]
+    fn test_latex_render_index() {
+        let renderer = LatexRenderer::new();
+        let tex = renderer.render_index(&gen_index()).unwrap().unwrap();
+
+        assert!(tex.starts_with("\\documentclass"));
+        assert!(tex.contains("\\title{test cookbook}"));
+        assert!(tex.contains("\\usepackage{multicol}\n"));
+        assert!(tex.contains("\\newcommand{\\ingredientsname}{Ingredients}\n"));
+        assert!(!tex.contains("minipage"));
+        assert!(tex.contains("\\chapter{test section}\n\n\\input{\\detokenize{test.md}}\n"));
+
+        let mut index = gen_index();
+        index.sections[0].entries[0].link = "50%_off.tex".to_string();
+        assert!(renderer.render_index(&index).is_err());
+        index.sections[0].entries[0].link = "fish_&_chips.tex".to_string();
+        let tex = renderer.render_index(&index).unwrap().unwrap();
+        assert!(tex.contains("\\input{\\detokenize{fish_&_chips.tex}}\n"));
+        assert!(tex.ends_with("\\end{document}\n"));
+    }
+
+    #[test]
+    fn test_latex_render_cookbook() {
+        let dir = temp_dir("latex");
+        fs::write(
+            dir.join("cookbook.yml"),
+            "title: Family & Friends\nchapters:\n  - title: Soups\n    recipes: [soup]",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("soup.yml"),
+            "name: Soup\nauthor: test\nservings: 1\ncook_minutes: 1\nsteps: []\ningredients: []",
+        )
+        .unwrap();
+
+        let cookbook = Cookbook::open(&dir).unwrap();
+        let tex = LatexRenderer::new().render_cookbook(&cookbook).unwrap();
+
+        assert!(tex.contains("\\title{Family \\& Friends}"));
+        assert!(tex.contains("\\chapter{Soups}\n\n\\begin{recipe}{Soup}"));
+
+        let renderer = LatexRenderer {
+            labels: crate::Locale::German.labels(),
+            ..Default::default()
+        };
+        let tex = renderer.render_cookbook(&cookbook).unwrap();
+        assert!(tex.contains("\\newcommand{\\methodname}{Zubereitung}\n"));
+    }
+}
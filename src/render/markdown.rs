@@ -0,0 +1,660 @@
+//! Renderer for Markdown output.
+
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use serde::Serialize;
+use serde_yaml::Value;
+
+use crate::render::{front_matter, prepare, FrontMatterFormat, FrontMatterStyle, Renderer};
+use crate::{Index, Locale, Recipe, SousError, UnitSystem};
+
+/// Text used by a [MarkdownRenderer] for headings and descriptions, also available to templates
+/// as `labels`.
+///
+/// The default labels are English. [Locale::labels] provides catalogs for other languages.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Labels {
+    /// Heading of the ingredient list.
+    pub ingredients: String,
+    /// Heading of the procedure.
+    pub method: String,
+    /// Follows the serving count.
+    pub servings: String,
+    /// Follows the preparation time in minutes.
+    pub prep: String,
+    /// Follows the cooking time in minutes.
+    pub cook: String,
+    /// Follows the total time of a recipe in an index.
+    pub minutes: String,
+    /// Header of the amount column in a table of ingredients.
+    pub amount: String,
+    /// Header of the unit column in a table of ingredients.
+    pub unit: String,
+    /// Header of the name column in a table of ingredients.
+    pub ingredient: String,
+    /// Precedes the author in plain-text output.
+    pub by: String,
+    /// Title of an index without a cookbook title.
+    pub index: String,
+    /// Heading of a table of contents.
+    pub contents: String,
+    /// Title of the only section of an index, and of a cookbook without a title.
+    pub recipes: String,
+    /// Title of the section of recipes outside every chapter.
+    pub other_recipes: String,
+    /// Title of the section of recipes without tags.
+    pub untagged: String,
+}
+
+impl Labels {
+    /// Create the default English labels.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Default for Labels {
+    fn default() -> Self {
+        Labels {
+            ingredients: "Ingredients".to_string(),
+            method: "Method".to_string(),
+            servings: "servings".to_string(),
+            prep: "minutes prep".to_string(),
+            cook: "minutes cook time".to_string(),
+            minutes: "minutes".to_string(),
+            amount: "Amount".to_string(),
+            unit: "Unit".to_string(),
+            ingredient: "Ingredient".to_string(),
+            by: "by".to_string(),
+            index: "Index".to_string(),
+            contents: "Contents".to_string(),
+            recipes: "Recipes".to_string(),
+            other_recipes: "Other Recipes".to_string(),
+            untagged: "Untagged".to_string(),
+        }
+    }
+}
+
+/// Layout of the ingredient list rendered by a [MarkdownRenderer].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum IngredientStyle {
+    /// A bulleted list using `*`.
+    #[default]
+    Asterisk,
+    /// A bulleted list using `-`.
+    Dash,
+    /// A task list using `- [ ]`, for ticking off ingredients while shopping or cooking.
+    Checkbox,
+    /// A table with amount, unit and name columns.
+    Table,
+}
+
+impl IngredientStyle {
+    /// Marker starting each list item.
+    fn bullet(&self) -> &'static str {
+        match self {
+            IngredientStyle::Asterisk | IngredientStyle::Table => "*",
+            IngredientStyle::Dash => "-",
+            IngredientStyle::Checkbox => "- [ ]",
+        }
+    }
+
+    /// Bullet starting a list item that is not an ingredient, never a checkbox.
+    fn plain_bullet(&self) -> &'static str {
+        match self {
+            IngredientStyle::Asterisk | IngredientStyle::Table => "*",
+            IngredientStyle::Dash | IngredientStyle::Checkbox => "-",
+        }
+    }
+}
+
+impl fmt::Display for IngredientStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IngredientStyle::Asterisk => write!(f, "asterisk"),
+            IngredientStyle::Dash => write!(f, "dash"),
+            IngredientStyle::Checkbox => write!(f, "checkbox"),
+            IngredientStyle::Table => write!(f, "table"),
+        }
+    }
+}
+
+impl FromStr for IngredientStyle {
+    type Err = SousError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "asterisk" => Ok(IngredientStyle::Asterisk),
+            "dash" => Ok(IngredientStyle::Dash),
+            "checkbox" => Ok(IngredientStyle::Checkbox),
+            "table" => Ok(IngredientStyle::Table),
+            _ => Err(SousError::UnknownIngredientStyle(s.to_string())),
+        }
+    }
+}
+
+/// Renders recipes in Markdown format.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MarkdownRenderer {
+    /// Whether to write the recipe's metadata as front matter instead of pure Markdown.
+    pub front_matter: bool,
+    /// Keys used in front matter, following a static site generator's conventions.
+    pub front_matter_style: FrontMatterStyle,
+    /// Whether front matter is written as YAML or TOML.
+    pub front_matter_format: FrontMatterFormat,
+    /// Whether to output meta information.
+    pub skip_meta: bool,
+    /// Whether to output ingredient list.
+    pub skip_ingredients: bool,
+    /// Whether to output the procedure.
+    pub skip_steps: bool,
+    /// Whether to list the recipe's [extra fields](crate::Metadata::extra) below its servings and
+    /// times. Front matter always includes them.
+    pub extra_fields: bool,
+    /// Optionally override the serving count when outputting.
+    pub servings: Option<u32>,
+    /// Optionally convert ingredient amounts into a [UnitSystem].
+    pub units: Option<UnitSystem>,
+    /// Optionally render translated text in this language instead of each recipe's default
+    /// language.
+    pub language: Option<String>,
+    /// Text used for headings and descriptions.
+    pub labels: Labels,
+    /// Locale used to format ingredient amounts.
+    ///
+    /// Labels are set separately, e.g. to `locale.labels()`.
+    pub locale: Locale,
+    /// Whether to write ingredient amounts as common fractions (e.g. "1 1/2") and pluralize units
+    /// written out as words (e.g. "2 cups") instead of writing them as given.
+    pub fractions: bool,
+    /// Layout of the ingredient list.
+    pub ingredient_style: IngredientStyle,
+    /// Heading level of the recipe or index title, 1 by default.
+    ///
+    /// Raise it to embed rendered recipes in a larger document.
+    pub title_level: usize,
+    /// Heading level of the ingredient, method and index sections, 2 by default.
+    pub section_level: usize,
+}
+
+impl MarkdownRenderer {
+    /// Create default render self.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Format an ingredient amount, as a fraction if enabled.
+    fn amount(&self, amount: f32) -> String {
+        if self.fractions {
+            self.locale.fraction(amount)
+        } else {
+            self.locale.decimal(amount)
+        }
+    }
+
+    /// Format an ingredient unit, pluralized if enabled.
+    fn unit(&self, unit: &str, amount: Option<f32>) -> String {
+        if self.fractions {
+            self.locale.pluralize_unit(unit, amount.unwrap_or(1.0))
+        } else {
+            unit.to_string()
+        }
+    }
+
+    /// Write an ATX heading of the given level.
+    fn heading(&self, output: &mut String, level: usize, text: &str) {
+        writeln!(output, "{} {}", "#".repeat(level.clamp(1, 6)), text).unwrap();
+    }
+}
+
+impl Default for MarkdownRenderer {
+    fn default() -> Self {
+        MarkdownRenderer {
+            front_matter: false,
+            front_matter_style: FrontMatterStyle::default(),
+            front_matter_format: FrontMatterFormat::default(),
+            skip_meta: false,
+            skip_ingredients: false,
+            skip_steps: false,
+            extra_fields: false,
+            servings: None,
+            units: None,
+            language: None,
+            labels: Labels::default(),
+            locale: Locale::default(),
+            fractions: false,
+            ingredient_style: IngredientStyle::default(),
+            title_level: 1,
+            section_level: 2,
+        }
+    }
+}
+
+impl Renderer for MarkdownRenderer {
+    fn render(&self, recipe: &Recipe) -> Result<String, SousError> {
+        let mut output = String::new();
+
+        let recipe = prepare(recipe, self.language.as_deref(), self.servings, self.units);
+        let servings = recipe.metadata.servings;
+
+        if !self.skip_meta {
+            if self.front_matter {
+                output.push_str(&front_matter(
+                    &recipe.metadata,
+                    self.front_matter_style,
+                    self.front_matter_format,
+                )?);
+                output.push('\n');
+            } else {
+                self.heading(&mut output, self.title_level, recipe.metadata.name.as_str());
+
+                write!(output, "**{}", recipe.metadata.author).unwrap();
+                if let Some(url) = &recipe.metadata.url {
+                    write!(output, " | {}", url).unwrap();
+                }
+                writeln!(output, "**").unwrap();
+
+                let labels = &self.labels;
+                write!(output, "**{} {}", servings, labels.servings).unwrap();
+                if let Some(prep) = &recipe.metadata.prep_minutes {
+                    write!(output, " | {} {}", prep, labels.prep).unwrap();
+                }
+                write!(
+                    output,
+                    " | {} {}**\n\n",
+                    recipe.metadata.cook_minutes, labels.cook
+                )
+                .unwrap();
+
+                if self.extra_fields && !recipe.metadata.extra.is_empty() {
+                    for (key, value) in recipe.metadata.extra.iter() {
+                        writeln!(output, "* {}: {}", key, value_text(value)).unwrap();
+                    }
+                    output.push('\n');
+                }
+            }
+        }
+
+        if !self.skip_ingredients {
+            self.heading(&mut output, self.section_level, &self.labels.ingredients);
+            if self.ingredient_style == IngredientStyle::Table {
+                writeln!(
+                    output,
+                    "| {} | {} | {} |\n| ---: | --- | --- |",
+                    self.labels.amount, self.labels.unit, self.labels.ingredient
+                )
+                .unwrap();
+                for ingredient in recipe.ingredients.iter() {
+                    let amount = ingredient
+                        .amount
+                        .map(|amount| self.amount(amount))
+                        .unwrap_or_default();
+                    let unit = ingredient
+                        .unit
+                        .as_ref()
+                        .map(|unit| self.unit(unit, ingredient.amount))
+                        .unwrap_or_default();
+                    writeln!(
+                        output,
+                        "| {} | {} | {} |",
+                        cell(&amount),
+                        cell(&unit),
+                        cell(ingredient.name.as_str())
+                    )
+                    .unwrap();
+                }
+            } else {
+                let bullet = self.ingredient_style.bullet();
+                for ingredient in recipe.ingredients.iter() {
+                    write!(output, "{}", bullet).unwrap();
+                    if let Some(amount) = ingredient.amount {
+                        write!(output, " {}", self.amount(amount)).unwrap();
+                    }
+                    if let Some(unit) = &ingredient.unit {
+                        write!(output, " {}", self.unit(unit, ingredient.amount)).unwrap();
+                    }
+                    writeln!(output, " {}", ingredient.name).unwrap();
+                }
+            }
+            output.push('\n');
+        }
+
+        if !self.skip_steps {
+            self.heading(&mut output, self.section_level, &self.labels.method);
+            for (i, step) in recipe.steps.iter().enumerate() {
+                writeln!(output, "{}. {}", i + 1, step).unwrap();
+            }
+        }
+
+        output.push('\n');
+        Ok(output)
+    }
+
+    fn extension(&self) -> &str {
+        "md"
+    }
+
+    fn render_index(&self, index: &Index) -> Result<Option<String>, SousError> {
+        let mut output = String::new();
+
+        let title = index.title.as_ref().unwrap_or(&self.labels.index);
+        self.heading(&mut output, self.title_level, title);
+        if let Some(author) = &index.author {
+            writeln!(output, "**{}**", author).unwrap();
+        }
+        if let Some(description) = &index.description {
+            writeln!(output, "\n{}", description).unwrap();
+        }
+
+        for section in index.sections.iter() {
+            output.push('\n');
+            self.heading(&mut output, self.section_level, &section.title);
+            for entry in section.entries.iter() {
+                writeln!(
+                    output,
+                    "{} [{}]({}) | {} {} | {} {}",
+                    self.ingredient_style.plain_bullet(),
+                    link_text(&entry.name),
+                    link_destination(&entry.link),
+                    self.servings.unwrap_or(entry.servings),
+                    self.labels.servings,
+                    entry.total_minutes,
+                    self.labels.minutes
+                )
+                .unwrap();
+            }
+        }
+
+        output.push('\n');
+        Ok(Some(output))
+    }
+}
+
+/// Escape text for a table cell.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// Escape text for the text of an inline link.
+fn link_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+/// Format the destination of an inline link, enclosing it in angle brackets if it contains
+/// spaces, parentheses or angle brackets.
+fn link_destination(link: &str) -> String {
+    if !link.contains(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | '<' | '>')) {
+        return link.to_string();
+    }
+
+    let mut escaped = String::from("<");
+    for c in link.chars() {
+        if matches!(c, '\\' | '<' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped.push('>');
+    escaped
+}
+
+/// Format a field value as inline text, joining lists with commas.
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(value) => value.to_string(),
+        Value::String(value) => value.clone(),
+        Value::Sequence(values) => values.iter().map(value_text).collect::<Vec<_>>().join(", "),
+        Value::Mapping(_) | Value::Tagged(_) => serde_json::to_string(value).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::tests::{gen_index, gen_recipe};
+
+    #[test]
+    fn test_md_render() {
+        let recipe = gen_recipe();
+
+        let renderer = MarkdownRenderer::new();
+        let md = renderer.render(&recipe).unwrap();
+
+        assert!(md.contains("# test recipe\n**test author**\n**1 servings | 1 minutes cook time**"));
+        assert!(md.contains("## Ingredients\n* 1 test ingredient"));
+        assert!(md.contains("## Method\n1. Step one"));
+        assert_eq!(renderer.extension(), "md");
+        assert_eq!(renderer.mime_type(), "text/markdown");
+    }
+
+    #[test]
+    fn test_md_render_front_matter() {
+        let recipe = gen_recipe();
+
+        let renderer = MarkdownRenderer {
+            front_matter: true,
+            ..Default::default()
+        };
+        let md = renderer.render(&recipe).unwrap();
+
+        assert!(md.starts_with(
+            "---\ntitle: test recipe\nauthor: test author\nservings: 1\ncook_minutes: 1\n\
+             total_minutes: 1\n---\n\n## Ingredients\n"
+        ));
+        assert!(!md.contains("**"));
+    }
+
+    #[test]
+    fn test_md_render_front_matter_labels() {
+        let mut recipe = gen_recipe();
+        recipe.metadata.tags = vec!["quick".to_string(), "summer".to_string()];
+        recipe.metadata.course = Some("main".to_string());
+        recipe.metadata.dietary = vec!["vegan".to_string()];
+
+        let renderer = MarkdownRenderer {
+            front_matter: true,
+            ..Default::default()
+        };
+        let md = renderer.render(&recipe).unwrap();
+
+        assert!(md.contains("tags:\n- quick\n- summer\ncourse: main\ndietary:\n- vegan\n---"));
+    }
+
+    #[test]
+    fn test_md_render_front_matter_toml() {
+        let renderer = MarkdownRenderer {
+            front_matter: true,
+            front_matter_style: FrontMatterStyle::Zola,
+            front_matter_format: FrontMatterFormat::Toml,
+            ..Default::default()
+        };
+        let md = renderer.render(&gen_recipe()).unwrap();
+
+        assert!(md.starts_with("+++\ntitle = \"test recipe\"\nauthors = [\"test author\"]\n"));
+        assert!(md.contains("\n[extra]\nservings = 1\n"));
+        assert!(md.contains("+++\n\n## Ingredients\n"));
+    }
+
+    #[test]
+    fn test_md_render_extra_fields() {
+        let mut recipe = gen_recipe();
+        recipe
+            .metadata
+            .extra
+            .insert("notes".to_string(), Value::from(vec!["crispy", "hot"]));
+        recipe
+            .metadata
+            .extra
+            .insert("rating".to_string(), Value::from(4.5));
+
+        let md = MarkdownRenderer::new().render(&recipe).unwrap();
+        assert!(!md.contains("rating"));
+
+        let renderer = MarkdownRenderer {
+            extra_fields: true,
+            ..Default::default()
+        };
+        let md = renderer.render(&recipe).unwrap();
+        assert!(md.contains(
+            "1 minutes cook time**\n\n* notes: crispy, hot\n* rating: 4.5\n\n## Ingredients"
+        ));
+    }
+
+    #[test]
+    fn test_md_render_servings() {
+        let recipe = gen_recipe();
+
+        let renderer = MarkdownRenderer {
+            servings: Some(2),
+            ..Default::default()
+        };
+        let md = renderer.render(&recipe).unwrap();
+
+        assert!(md.contains("2 servings"));
+        assert!(md.contains("2 test ingredient"));
+    }
+
+    #[test]
+    fn test_md_render_units() {
+        let mut recipe = gen_recipe();
+        recipe.ingredients[0].unit = Some("cup".to_string());
+
+        let renderer = MarkdownRenderer {
+            units: Some(UnitSystem::Metric),
+            ..Default::default()
+        };
+        let md = renderer.render(&recipe).unwrap();
+
+        assert!(md.contains("* 237 ml test ingredient"));
+    }
+
+    #[test]
+    fn test_md_render_index() {
+        let index = gen_index();
+
+        let renderer = MarkdownRenderer::new();
+        let md = renderer.render_index(&index).unwrap().unwrap();
+
+        assert!(md.contains("# test cookbook\n"));
+        assert!(md.contains("## test section\n* [test recipe](test.md) | 1 servings | 1 minutes"));
+    }
+
+    #[test]
+    fn test_md_render_index_links() {
+        let mut index = gen_index();
+        index.sections[0].entries[0].name = "[best] stew".to_string();
+        index.sections[0].entries[0].link = "stew (old).md".to_string();
+
+        let md = MarkdownRenderer::new()
+            .render_index(&index)
+            .unwrap()
+            .unwrap();
+
+        assert!(md.contains("* [\\[best\\] stew](<stew (old).md>) |"));
+        assert_eq!(link_destination("a<b>.md"), "<a\\<b\\>.md>");
+    }
+
+    #[test]
+    fn test_md_render_labels() {
+        let recipe = gen_recipe();
+
+        let renderer = MarkdownRenderer {
+            labels: Labels {
+                ingredients: "You will need".to_string(),
+                servings: "portions".to_string(),
+                ..Default::default()
+            },
+            title_level: 2,
+            section_level: 4,
+            ingredient_style: IngredientStyle::Checkbox,
+            ..Default::default()
+        };
+        let md = renderer.render(&recipe).unwrap();
+
+        assert!(md.starts_with("## test recipe\n"));
+        assert!(md.contains("**1 portions | 1 minutes cook time**"));
+        assert!(md.contains("#### You will need\n- [ ] 1 test ingredient\n"));
+        assert!(md.contains("#### Method\n1. Step one"));
+    }
+
+    #[test]
+    fn test_md_render_locale() {
+        let mut recipe = gen_recipe();
+        recipe.ingredients[0].amount = Some(0.15);
+
+        let renderer = MarkdownRenderer {
+            labels: Locale::German.labels(),
+            locale: Locale::German,
+            ..Default::default()
+        };
+        let md = renderer.render(&recipe).unwrap();
+
+        assert!(md.contains("**1 Portionen | 1 Minuten Kochzeit**"));
+        assert!(md.contains("## Zutaten\n* 0,15 test ingredient\n"));
+        assert!(md.contains("## Zubereitung\n"));
+    }
+
+    #[test]
+    fn test_md_render_table() {
+        let mut recipe = gen_recipe();
+        recipe.ingredients[0].amount = Some(1.5);
+        recipe.ingredients[0].unit = Some("cup".to_string());
+
+        let renderer = MarkdownRenderer {
+            ingredient_style: IngredientStyle::Table,
+            ..Default::default()
+        };
+        let md = renderer.render(&recipe).unwrap();
+
+        assert!(md.contains(
+            "## Ingredients\n| Amount | Unit | Ingredient |\n| ---: | --- | --- |\n\
+             | 1.5 | cup | test ingredient |\n"
+        ));
+
+        let renderer = MarkdownRenderer {
+            fractions: true,
+            ..renderer
+        };
+        let md = renderer.render(&recipe).unwrap();
+
+        assert!(md.contains("| 1 1/2 | cups | test ingredient |\n"));
+
+        recipe.ingredients[0].unit = Some("cup|mug".to_string());
+        recipe.ingredients[0].name = "salt|pepper".into();
+        let md = renderer.render(&recipe).unwrap();
+
+        assert!(md.contains("| 1 1/2 | cup\\|mug | salt\\|pepper |\n"));
+    }
+
+    #[test]
+    fn test_md_render_index_checkbox() {
+        let renderer = MarkdownRenderer {
+            ingredient_style: IngredientStyle::Checkbox,
+            ..Default::default()
+        };
+        let md = renderer.render_index(&gen_index()).unwrap().unwrap();
+
+        assert!(md.contains("## test section\n- [test recipe](test.md) | 1 servings"));
+        assert!(!md.contains("[ ]"));
+    }
+
+    #[test]
+    fn test_ingredient_style_from_str() {
+        assert_eq!(
+            "Checkbox".parse::<IngredientStyle>().unwrap(),
+            IngredientStyle::Checkbox
+        );
+        assert!("numbered".parse::<IngredientStyle>().is_err());
+    }
+}
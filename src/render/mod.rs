@@ -0,0 +1,143 @@
+//! Types for rendering recipes to other formats.
+
+mod asciidoc;
+mod filters;
+mod front_matter;
+mod html;
+mod latex;
+mod markdown;
+mod pdf;
+mod rst;
+mod template;
+mod text;
+mod theme;
+
+use std::io;
+
+use crate::{Index, IndexSection, Recipe, SousError, UnitSystem};
+
+pub use asciidoc::AsciiDocRenderer;
+pub use front_matter::{front_matter, FrontMatterFormat, FrontMatterStyle};
+pub use html::HtmlRenderer;
+pub use latex::LatexRenderer;
+pub use markdown::{IngredientStyle, Labels, MarkdownRenderer};
+pub use pdf::{PageSize, PdfRenderer};
+pub use rst::RstRenderer;
+pub use template::TemplateRenderer;
+pub use text::TextRenderer;
+
+/// A type that can render a recipe to a String or bytes.
+pub trait Renderer {
+    /// Construct a String representation of the provided [Recipe].
+    ///
+    /// Renderers producing binary formats return [SousError::BinaryOutput] and should be used
+    /// through [Renderer::render_bytes] or [Renderer::render_to] instead.
+    fn render(&self, recipe: &Recipe) -> Result<String, SousError>;
+
+    /// Construct the provided [Recipe] as bytes in the renderer's format.
+    fn render_bytes(&self, recipe: &Recipe) -> Result<Vec<u8>, SousError> {
+        Ok(self.render(recipe)?.into_bytes())
+    }
+
+    /// Write the provided [Recipe] to `writer` in the renderer's format.
+    fn render_to(&self, recipe: &Recipe, writer: &mut dyn io::Write) -> Result<(), SousError> {
+        writer.write_all(&self.render_bytes(recipe)?)?;
+        Ok(())
+    }
+
+    /// Preferred file extension for rendered output, without a leading dot.
+    fn extension(&self) -> &str {
+        "txt"
+    }
+
+    /// MIME type of rendered output.
+    fn mime_type(&self) -> &str {
+        mime_type(self.extension())
+    }
+
+    /// Construct a String representation of the provided cookbook [Index].
+    ///
+    /// Returns [None] if the renderer is unable to render indexes.
+    fn render_index(&self, _index: &Index) -> Result<Option<String>, SousError> {
+        Ok(None)
+    }
+
+    /// Construct a String representation of a page listing the recipes with a tag.
+    ///
+    /// The page is expected to be placed in a subdirectory of the output root. Returns [None] if
+    /// the renderer is unable to render tag pages.
+    fn render_tag(&self, _tag: &IndexSection) -> Result<Option<String>, SousError> {
+        Ok(None)
+    }
+}
+
+/// Apply a renderer's settings to a recipe: resolve its translations into `language` (or the
+/// recipe's default language), then scale it to `servings` and convert it into `units`.
+pub(crate) fn prepare(
+    recipe: &Recipe,
+    language: Option<&str>,
+    servings: Option<u32>,
+    units: Option<UnitSystem>,
+) -> Recipe {
+    let mut recipe = recipe.localized(language.unwrap_or(recipe.default_language()));
+    if let Some(servings) = servings {
+        recipe = recipe.scaled(servings);
+    }
+    if let Some(units) = units {
+        recipe = recipe.to_system(units);
+    }
+    recipe
+}
+
+/// Guess the MIME type of output with the provided file extension.
+fn mime_type(extension: &str) -> &'static str {
+    match extension.to_lowercase().as_str() {
+        "md" | "markdown" => "text/markdown",
+        "rst" => "text/x-rst",
+        "adoc" | "asciidoc" => "text/asciidoc",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "json" => "application/json",
+        "yml" | "yaml" => "application/yaml",
+        "xml" => "application/xml",
+        "pdf" => "application/pdf",
+        "tex" => "application/x-tex",
+        "csv" => "text/csv",
+        _ => "text/plain",
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{Index, IndexEntry, IndexSection, Ingredient, Metadata, Recipe};
+
+    pub fn gen_recipe() -> Recipe {
+        Recipe {
+            metadata: Metadata {
+                name: "test recipe".into(),
+                author: "test author".to_string(),
+                servings: 1,
+                cook_minutes: 1,
+                ..Default::default()
+            },
+            steps: vec!["Step one".into()],
+            ingredients: vec![Ingredient {
+                name: "test ingredient".into(),
+                amount: Some(1.0),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    pub fn gen_index() -> Index {
+        Index {
+            title: Some("test cookbook".to_string()),
+            sections: vec![IndexSection {
+                title: "test section".to_string(),
+                entries: vec![IndexEntry::new(&gen_recipe(), "test.md".to_string(), None)],
+            }],
+            ..Default::default()
+        }
+    }
+}
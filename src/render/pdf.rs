@@ -0,0 +1,602 @@
+//! Renderer for PDF output.
+
+use std::fmt;
+use std::str::FromStr;
+
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+
+use crate::format::duration;
+use crate::render::{prepare, Labels, Renderer};
+use crate::{Cookbook, Index, Recipe, SousError, UnitSystem};
+
+/// Size of the pages produced by a [PdfRenderer].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum PageSize {
+    /// A 4x6 inch index card, in landscape orientation.
+    Card4x6,
+    /// A 5x8 inch index card, in landscape orientation.
+    Card5x8,
+    /// An A4 page.
+    A4,
+    /// A US Letter page.
+    #[default]
+    Letter,
+}
+
+impl PageSize {
+    /// Width and height of the page in points.
+    pub fn dimensions(&self) -> (f32, f32) {
+        match self {
+            PageSize::Card4x6 => (432.0, 288.0),
+            PageSize::Card5x8 => (576.0, 360.0),
+            PageSize::A4 => (595.28, 841.89),
+            PageSize::Letter => (612.0, 792.0),
+        }
+    }
+
+    fn style(&self) -> Style {
+        match self {
+            PageSize::Card4x6 | PageSize::Card5x8 => Style {
+                margin: 20.0,
+                title: 14.0,
+                heading: 10.0,
+                body: 8.0,
+            },
+            PageSize::A4 | PageSize::Letter => Style {
+                margin: 56.0,
+                title: 22.0,
+                heading: 14.0,
+                body: 11.0,
+            },
+        }
+    }
+}
+
+impl fmt::Display for PageSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PageSize::Card4x6 => write!(f, "4x6"),
+            PageSize::Card5x8 => write!(f, "5x8"),
+            PageSize::A4 => write!(f, "a4"),
+            PageSize::Letter => write!(f, "letter"),
+        }
+    }
+}
+
+impl FromStr for PageSize {
+    type Err = SousError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "4x6" => Ok(PageSize::Card4x6),
+            "5x8" => Ok(PageSize::Card5x8),
+            "a4" => Ok(PageSize::A4),
+            "letter" => Ok(PageSize::Letter),
+            _ => Err(SousError::UnknownPageSize(s.to_string())),
+        }
+    }
+}
+
+/// Renders recipes as printable PDF documents, either one recipe at a time or a whole
+/// [Cookbook] with a title page and table of contents.
+///
+/// Text is set in the standard Helvetica fonts, so no font files are required.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct PdfRenderer {
+    /// Size of the pages.
+    pub size: PageSize,
+    /// Optionally override the serving count when outputting.
+    pub servings: Option<u32>,
+    /// Optionally convert ingredient amounts into a [UnitSystem].
+    pub units: Option<UnitSystem>,
+    /// Optionally render translated text in this language instead of each recipe's default
+    /// language.
+    pub language: Option<String>,
+    /// Text of headings and labels.
+    pub labels: Labels,
+}
+
+impl PdfRenderer {
+    /// Create default render self.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Construct a PDF document containing every recipe in the provided [Cookbook], preceded by
+    /// a title page and a table of contents grouped by chapter. Every recipe starts on a new page.
+    pub fn render_cookbook(&self, cookbook: &Cookbook) -> Result<Vec<u8>, SousError> {
+        let index = cookbook.index("pdf", self.language.as_deref(), &self.labels)?;
+        let title = index.title.as_ref().unwrap_or(&self.labels.recipes);
+
+        let mut recipes = Layout::new(self.size);
+        let mut starts = Vec::new();
+        for section in index.sections.iter() {
+            for entry in section.entries.iter() {
+                let recipe = cookbook.load_recipe(&entry.file)?;
+                starts.push(recipes.pages.len());
+                self.layout_recipe(&mut recipes, &recipe);
+            }
+        }
+
+        // The table of contents is laid out once to find its length, then again with the page
+        // numbers that length implies.
+        let contents_pages = self
+            .layout_contents(&index, &vec![0; starts.len()])
+            .pages
+            .len();
+        let numbers: Vec<usize> = starts
+            .iter()
+            .map(|start| 1 + contents_pages + start + 1)
+            .collect();
+        let contents = self.layout_contents(&index, &numbers);
+
+        let mut layout = Layout::new(self.size);
+        layout.new_page();
+        layout.space(layout.height / 4.0);
+        layout.paragraph(Font::Bold, layout.style.title * 1.5, title);
+        if let Some(author) = &index.author {
+            layout.space(layout.style.body);
+            layout.paragraph(Font::Regular, layout.style.heading, author);
+        }
+        if let Some(description) = &index.description {
+            layout.space(layout.style.body * 2.0);
+            layout.paragraph(Font::Regular, layout.style.body, description);
+        }
+
+        layout.pages.extend(contents.pages);
+        layout.pages.extend(recipes.pages);
+        layout.number_pages();
+
+        Ok(write(&layout, title))
+    }
+
+    fn layout_recipe(&self, layout: &mut Layout, recipe: &Recipe) {
+        let recipe = prepare(recipe, self.language.as_deref(), self.servings, self.units);
+        let style = layout.style;
+
+        layout.new_page();
+        layout.paragraph(Font::Bold, style.title, recipe.metadata.name.as_str());
+
+        let mut meta = vec![recipe.metadata.author.clone()];
+        meta.push(format!("{} servings", recipe.metadata.servings));
+        if let Some(prep) = recipe.metadata.prep_minutes {
+            meta.push(format!("{} prep", duration(prep)));
+        }
+        meta.push(format!(
+            "{} cook time",
+            duration(recipe.metadata.cook_minutes)
+        ));
+        layout.paragraph(Font::Regular, style.body, &meta.join(" | "));
+
+        layout.space(style.body);
+        layout.keep(style.heading * 2.0 + style.body * 2.0);
+        layout.paragraph(Font::Bold, style.heading, &self.labels.ingredients);
+        for ingredient in recipe.ingredients.iter() {
+            layout.item(style.body, "\u{2022}", &ingredient.to_string());
+        }
+
+        layout.space(style.body);
+        layout.keep(style.heading * 2.0 + style.body * 2.0);
+        layout.paragraph(Font::Bold, style.heading, &self.labels.method);
+        for (i, step) in recipe.steps.iter().enumerate() {
+            layout.item(style.body, &format!("{}.", i + 1), step.as_str());
+        }
+    }
+
+    fn layout_contents(&self, index: &Index, numbers: &[usize]) -> Layout {
+        let mut layout = Layout::new(self.size);
+        let style = layout.style;
+        let mut numbers = numbers.iter();
+
+        layout.new_page();
+        layout.paragraph(Font::Bold, style.title, &self.labels.contents);
+        for section in index.sections.iter() {
+            layout.space(style.body);
+            layout.keep(style.heading * 2.0 + style.body * 2.0);
+            layout.paragraph(Font::Bold, style.heading, &section.title);
+            for entry in section.entries.iter() {
+                let number = numbers.next().copied().unwrap_or_default();
+                layout.entry(style.body, &entry.name, &number.to_string());
+            }
+        }
+
+        layout
+    }
+}
+
+impl Renderer for PdfRenderer {
+    fn render(&self, _recipe: &Recipe) -> Result<String, SousError> {
+        Err(SousError::BinaryOutput)
+    }
+
+    fn render_bytes(&self, recipe: &Recipe) -> Result<Vec<u8>, SousError> {
+        let mut layout = Layout::new(self.size);
+        self.layout_recipe(&mut layout, recipe);
+
+        Ok(write(&layout, recipe.metadata.name.as_str()))
+    }
+
+    fn extension(&self) -> &str {
+        "pdf"
+    }
+}
+
+/// Font sizes and margins for a [PageSize], in points.
+#[derive(Clone, Copy, Debug)]
+struct Style {
+    margin: f32,
+    title: f32,
+    heading: f32,
+    body: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(&self) -> Name<'static> {
+        match self {
+            Font::Regular => Name(b"F1"),
+            Font::Bold => Name(b"F2"),
+        }
+    }
+
+    /// Width of the text in points when set in this font at the given size.
+    fn width(&self, text: &str, size: f32) -> f32 {
+        let widths = match self {
+            Font::Regular => &HELVETICA_WIDTHS,
+            Font::Bold => &HELVETICA_BOLD_WIDTHS,
+        };
+
+        let units: u32 = encode(text)
+            .iter()
+            .map(|&byte| match byte {
+                32..=126 => widths[byte as usize - 32] as u32,
+                0x95 => 350,
+                _ => 556,
+            })
+            .sum();
+
+        units as f32 * size / 1000.0
+    }
+}
+
+/// A run of text placed on a page, with its baseline starting at `x`, `y`.
+#[derive(Clone, Debug)]
+struct Span {
+    font: Font,
+    size: f32,
+    x: f32,
+    y: f32,
+    text: String,
+}
+
+/// Flows text onto pages from top to bottom, starting new pages as needed.
+struct Layout {
+    style: Style,
+    width: f32,
+    height: f32,
+    pages: Vec<Vec<Span>>,
+    y: f32,
+}
+
+impl Layout {
+    fn new(size: PageSize) -> Self {
+        let (width, height) = size.dimensions();
+
+        Layout {
+            style: size.style(),
+            width,
+            height,
+            pages: Vec::new(),
+            y: 0.0,
+        }
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(Vec::new());
+        self.y = self.height - self.style.margin;
+    }
+
+    fn space(&mut self, amount: f32) {
+        self.y -= amount;
+    }
+
+    /// Start a new page unless `amount` points of space remain on the current one.
+    fn keep(&mut self, amount: f32) {
+        if self.pages.is_empty() || self.y - amount < self.style.margin {
+            self.new_page();
+        }
+    }
+
+    /// Move to the next line of text at the given size, returning its baseline.
+    fn line(&mut self, size: f32) -> f32 {
+        self.keep(size * 1.3);
+        self.y -= size * 1.3;
+        self.y
+    }
+
+    fn push(&mut self, font: Font, size: f32, x: f32, y: f32, text: &str) {
+        let page = self.pages.last_mut().expect("layout has a page");
+        page.push(Span {
+            font,
+            size,
+            x,
+            y,
+            text: text.to_string(),
+        });
+    }
+
+    /// Lay out wrapped text from the left margin.
+    fn paragraph(&mut self, font: Font, size: f32, text: &str) {
+        let x = self.style.margin;
+        for line in wrap(font, size, text, self.width - self.style.margin - x) {
+            let y = self.line(size);
+            self.push(font, size, x, y, &line);
+        }
+    }
+
+    /// Lay out a list item, with the marker hanging to the left of the wrapped text.
+    fn item(&mut self, size: f32, marker: &str, text: &str) {
+        let indent = size * 2.0;
+        let x = self.style.margin + indent;
+        for (i, line) in wrap(
+            Font::Regular,
+            size,
+            text,
+            self.width - self.style.margin - x,
+        )
+        .iter()
+        .enumerate()
+        {
+            let y = self.line(size);
+            if i == 0 {
+                let marker_x = x - Font::Regular.width(marker, size) - size / 2.0;
+                self.push(Font::Regular, size, marker_x, y, marker);
+            }
+            self.push(Font::Regular, size, x, y, line);
+        }
+    }
+
+    /// Lay out a table of contents entry, with the page number aligned to the right margin.
+    fn entry(&mut self, size: f32, name: &str, number: &str) {
+        let right = self.width - self.style.margin;
+        let x = self.style.margin + size;
+        let lines = wrap(Font::Regular, size, name, right - x - size * 3.0);
+        for (i, line) in lines.iter().enumerate() {
+            let y = self.line(size);
+            self.push(Font::Regular, size, x, y, line);
+            if i == lines.len() - 1 {
+                let number_x = right - Font::Regular.width(number, size);
+                self.push(Font::Regular, size, number_x, y, number);
+            }
+        }
+    }
+
+    /// Add a page number to the foot of every page but the first.
+    fn number_pages(&mut self) {
+        let size = self.style.body;
+        let y = self.style.margin / 2.0;
+        for (i, page) in self.pages.iter_mut().enumerate().skip(1) {
+            let number = (i + 1).to_string();
+            page.push(Span {
+                font: Font::Regular,
+                size,
+                x: (self.width - Font::Regular.width(&number, size)) / 2.0,
+                y,
+                text: number,
+            });
+        }
+    }
+}
+
+/// Break text into lines no wider than `width`. Words longer than a line are left unbroken.
+fn wrap(font: Font, size: f32, text: &str, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if line.is_empty() {
+            word.to_string()
+        } else {
+            format!("{line} {word}")
+        };
+
+        if !line.is_empty() && font.width(&candidate, size) > width {
+            lines.push(std::mem::replace(&mut line, word.to_string()));
+        } else {
+            line = candidate;
+        }
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Encode text in the WinAnsi encoding used by the standard fonts, replacing characters it cannot
+/// represent with `?`.
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' | '\u{a0}'..='\u{ff}' => c as u8,
+            '\u{20ac}' => 0x80,
+            '\u{2026}' => 0x85,
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201c}' => 0x93,
+            '\u{201d}' => 0x94,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// Write the laid out pages as a PDF document.
+fn write(layout: &Layout, title: &str) -> Vec<u8> {
+    let catalog_id = Ref::new(1);
+    let tree_id = Ref::new(2);
+    let info_id = Ref::new(3);
+    let regular_id = Ref::new(4);
+    let bold_id = Ref::new(5);
+    let page_ids: Vec<Ref> = (0..layout.pages.len())
+        .map(|i| Ref::new(6 + 2 * i as i32))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(tree_id);
+    pdf.document_info(info_id)
+        .title(TextStr(title))
+        .creator(TextStr("sous"));
+    pdf.pages(tree_id)
+        .kids(page_ids.iter().copied())
+        .count(page_ids.len() as i32);
+
+    for (id, name) in [(regular_id, "Helvetica"), (bold_id, "Helvetica-Bold")] {
+        pdf.type1_font(id)
+            .base_font(Name(name.as_bytes()))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+    }
+
+    for (page_id, spans) in page_ids.iter().zip(layout.pages.iter()) {
+        let content_id = Ref::new(page_id.get() + 1);
+
+        let mut page = pdf.page(*page_id);
+        page.parent(tree_id)
+            .media_box(Rect::new(0.0, 0.0, layout.width, layout.height))
+            .contents(content_id);
+        page.resources()
+            .fonts()
+            .pair(Font::Regular.resource(), regular_id)
+            .pair(Font::Bold.resource(), bold_id);
+        page.finish();
+
+        let mut content = Content::new();
+        for span in spans {
+            content
+                .begin_text()
+                .set_font(span.font.resource(), span.size)
+                .next_line(span.x, span.y)
+                .show(Str(&encode(&span.text)))
+                .end_text();
+        }
+        pdf.stream(content_id, &content.finish());
+    }
+
+    pdf.finish()
+}
+
+/// Advance widths of the printable ASCII characters in Helvetica, in thousandths of an em.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Advance widths of the printable ASCII characters in Helvetica Bold, in thousandths of an em.
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722, 722, 722, 722, 667,
+    611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 333, 278, 333, 584, 556, 333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556,
+    278, 889, 611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::render::tests::gen_recipe;
+    use crate::tests::temp_dir;
+
+    fn page_count(pdf: &[u8]) -> usize {
+        String::from_utf8_lossy(pdf)
+            .matches("/Type /Page\n")
+            .count()
+    }
+
+    #[test]
+    fn test_page_size() {
+        assert_eq!("4x6".parse::<PageSize>().unwrap(), PageSize::Card4x6);
+        assert_eq!("A4".parse::<PageSize>().unwrap(), PageSize::A4);
+        assert!("legal".parse::<PageSize>().is_err());
+        assert_eq!(PageSize::Card5x8.to_string(), "5x8");
+    }
+
+    #[test]
+    fn test_wrap() {
+        let lines = wrap(Font::Regular, 10.0, "one two three four", 50.0);
+
+        assert_eq!(lines, vec!["one two", "three four"]);
+        assert_eq!(wrap(Font::Regular, 10.0, "", 50.0), vec![""]);
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("Crème – ☃"), b"Cr\xe8me \x96 ?");
+    }
+
+    #[test]
+    fn test_pdf_render() {
+        let renderer = PdfRenderer {
+            size: PageSize::Card4x6,
+            ..Default::default()
+        };
+        let pdf = renderer.render_bytes(&gen_recipe()).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(pdf.starts_with(b"%PDF-"));
+        assert!(text.contains("/MediaBox [0 0 432 288]"));
+        assert!(text.contains("(test recipe) Tj"));
+        assert!(text.contains("(1 test ingredient) Tj"));
+        assert_eq!(page_count(&pdf), 1);
+        assert_eq!(renderer.mime_type(), "application/pdf");
+        assert!(renderer.render(&gen_recipe()).is_err());
+    }
+
+    #[test]
+    fn test_pdf_render_long_recipe() {
+        let mut recipe = gen_recipe();
+        recipe.steps = vec!["Stir the pot for a while longer.".into(); 40];
+
+        let renderer = PdfRenderer {
+            size: PageSize::Card4x6,
+            ..Default::default()
+        };
+        let pdf = renderer.render_bytes(&recipe).unwrap();
+
+        assert!(page_count(&pdf) > 1);
+    }
+
+    #[test]
+    fn test_pdf_render_cookbook() {
+        let dir = temp_dir("pdf");
+        fs::write(dir.join("cookbook.yml"), "title: Family Recipes").unwrap();
+        for name in ["soup", "stew"] {
+            fs::write(
+                dir.join(format!("{name}.yml")),
+                format!("name: {name}\nauthor: test\nservings: 1\ncook_minutes: 1\nsteps: []\ningredients: []"),
+            )
+            .unwrap();
+        }
+
+        let cookbook = Cookbook::open(&dir).unwrap();
+        let pdf = PdfRenderer::new().render_cookbook(&cookbook).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert_eq!(page_count(&pdf), 4);
+        assert!(text.contains("(Family Recipes) Tj"));
+        assert!(text.contains("(Contents) Tj"));
+        assert!(text.contains("(stew) Tj"));
+        assert!(text.contains("(4) Tj"));
+    }
+}
@@ -0,0 +1,240 @@
+//! Renderer for reStructuredText output.
+
+use std::fmt::Write;
+
+use crate::render::{prepare, Labels, Renderer};
+use crate::{Index, Recipe, SousError, UnitSystem};
+
+/// Renders recipes in reStructuredText format, e.g. for Sphinx.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct RstRenderer {
+    /// Whether to use a field list (Sphinx file-wide metadata) instead of an author line.
+    pub front_matter: bool,
+    /// Whether to output meta information.
+    pub skip_meta: bool,
+    /// Whether to output ingredient list.
+    pub skip_ingredients: bool,
+    /// Whether to output the procedure.
+    pub skip_steps: bool,
+    /// Optionally override the serving count when outputting.
+    pub servings: Option<u32>,
+    /// Optionally convert ingredient amounts into a [UnitSystem].
+    pub units: Option<UnitSystem>,
+    /// Optionally render translated text in this language instead of each recipe's default
+    /// language.
+    pub language: Option<String>,
+    /// Text of headings and labels.
+    pub labels: Labels,
+}
+
+impl RstRenderer {
+    /// Create default render self.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Renderer for RstRenderer {
+    fn render(&self, recipe: &Recipe) -> Result<String, SousError> {
+        let mut output = String::new();
+
+        let recipe = prepare(recipe, self.language.as_deref(), self.servings, self.units);
+        let metadata = &recipe.metadata;
+
+        if self.front_matter && !self.skip_meta {
+            writeln!(output, ":author: {}", escape(&metadata.author)).unwrap();
+            if let Some(url) = &metadata.url {
+                writeln!(output, ":url: {}", escape(url)).unwrap();
+            }
+            if !metadata.tags.is_empty() {
+                writeln!(output, ":tags: {}", escape(&metadata.tags.join(", "))).unwrap();
+            }
+            if let Some(cuisine) = &metadata.cuisine {
+                writeln!(output, ":cuisine: {}", escape(cuisine)).unwrap();
+            }
+            if let Some(course) = &metadata.course {
+                writeln!(output, ":course: {}", escape(course)).unwrap();
+            }
+            if !metadata.dietary.is_empty() {
+                writeln!(output, ":dietary: {}", escape(&metadata.dietary.join(", "))).unwrap();
+            }
+            output.push('\n');
+        }
+
+        heading(&mut output, metadata.name.as_str(), '=');
+
+        if !self.skip_meta {
+            if !self.front_matter {
+                write!(output, "**{}", escape(&metadata.author)).unwrap();
+                if let Some(url) = &metadata.url {
+                    write!(output, " | {}", escape(url)).unwrap();
+                }
+                output.push_str("**\n\n");
+            }
+
+            let labels = &self.labels;
+            write!(
+                output,
+                "**{} {}",
+                metadata.servings,
+                escape(&labels.servings)
+            )
+            .unwrap();
+            if let Some(prep) = &metadata.prep_minutes {
+                write!(output, " | {} {}", prep, escape(&labels.prep)).unwrap();
+            }
+            write!(
+                output,
+                " | {} {}**\n\n",
+                metadata.cook_minutes,
+                escape(&labels.cook)
+            )
+            .unwrap();
+        }
+
+        if !self.skip_ingredients {
+            heading(&mut output, &self.labels.ingredients, '-');
+            for ingredient in recipe.ingredients.iter() {
+                writeln!(output, "* {}", escape(&ingredient.to_string())).unwrap();
+            }
+            output.push('\n');
+        }
+
+        if !self.skip_steps {
+            heading(&mut output, &self.labels.method, '-');
+            for (i, step) in recipe.steps.iter().enumerate() {
+                writeln!(output, "{}. {}", i + 1, escape(step.as_str())).unwrap();
+            }
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn extension(&self) -> &str {
+        "rst"
+    }
+
+    fn render_index(&self, index: &Index) -> Result<Option<String>, SousError> {
+        let mut output = String::new();
+
+        let title = index.title.as_ref().unwrap_or(&self.labels.index);
+        heading(&mut output, title, '=');
+        if let Some(author) = &index.author {
+            writeln!(output, "**{}**\n", escape(author)).unwrap();
+        }
+        if let Some(description) = &index.description {
+            writeln!(output, "{}\n", escape(description)).unwrap();
+        }
+
+        for section in index.sections.iter() {
+            heading(&mut output, &section.title, '-');
+            for entry in section.entries.iter() {
+                writeln!(
+                    output,
+                    "* `{} <{}>`__ | {} {} | {} {}",
+                    escape(&entry.name),
+                    entry.link,
+                    self.servings.unwrap_or(entry.servings),
+                    escape(&self.labels.servings),
+                    entry.total_minutes,
+                    escape(&self.labels.minutes)
+                )
+                .unwrap();
+            }
+            output.push('\n');
+        }
+
+        Ok(Some(output))
+    }
+}
+
+/// Write a section title underlined with `underline`, followed by a blank line.
+fn heading(output: &mut String, title: &str, underline: char) {
+    let title = escape(title);
+    let line: String = std::iter::repeat_n(underline, title.chars().count()).collect();
+
+    writeln!(output, "{title}\n{line}\n").unwrap();
+}
+
+/// Escape characters that start inline markup.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '`' | '_' | '|') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::tests::{gen_index, gen_recipe};
+    use crate::Locale;
+
+    #[test]
+    fn test_rst_render() {
+        let recipe = gen_recipe();
+
+        let renderer = RstRenderer::new();
+        let rst = renderer.render(&recipe).unwrap();
+
+        assert!(rst.starts_with("test recipe\n===========\n\n**test author**\n\n"));
+        assert!(rst.contains("**1 servings | 1 minutes cook time**"));
+        assert!(rst.contains("Ingredients\n-----------\n\n* 1 test ingredient\n"));
+        assert!(rst.contains("Method\n------\n\n1. Step one\n"));
+    }
+
+    #[test]
+    fn test_rst_render_front_matter() {
+        let mut recipe = gen_recipe();
+        recipe.metadata.tags = vec!["quick".to_string()];
+
+        let renderer = RstRenderer {
+            front_matter: true,
+            skip_steps: true,
+            ..Default::default()
+        };
+        let rst = renderer.render(&recipe).unwrap();
+
+        assert!(rst.starts_with(":author: test author\n:tags: quick\n\ntest recipe\n"));
+        assert!(!rst.contains("Method"));
+    }
+
+    #[test]
+    fn test_rst_escape() {
+        assert_eq!(escape("*bold* a_b"), "\\*bold\\* a\\_b");
+    }
+
+    #[test]
+    fn test_rst_render_index() {
+        let renderer = RstRenderer::new();
+        let rst = renderer.render_index(&gen_index()).unwrap().unwrap();
+
+        assert!(rst.starts_with("test cookbook\n=============\n\n"));
+        assert!(rst.contains(
+            "test section\n------------\n\n* `test recipe <test.md>`__ | 1 servings | 1 minutes"
+        ));
+    }
+
+    #[test]
+    fn test_rst_render_labels() {
+        let renderer = RstRenderer {
+            labels: Locale::German.labels(),
+            ..Default::default()
+        };
+        let rst = renderer.render(&gen_recipe()).unwrap();
+        assert!(rst.contains("**1 Portionen | 1 Minuten Kochzeit**"));
+        assert!(rst.contains("Zutaten\n-------\n"));
+
+        let mut index = gen_index();
+        index.title = None;
+        let rst = renderer.render_index(&index).unwrap().unwrap();
+        assert!(rst.starts_with("Verzeichnis\n===========\n\n"));
+        assert!(rst.contains("| 1 Portionen | 1 Minuten"));
+    }
+}
@@ -0,0 +1,432 @@
+//! Renderer for Tera template output.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use tera::{Context, Tera};
+
+use crate::render::{filters, prepare, theme, Renderer};
+use crate::{Index, IndexEntry, IndexSection, Locale, Recipe, SousError, UnitSystem};
+
+/// Renders recipes using [Tera] templates.
+///
+/// A renderer holds up to three named templates: `recipe` for recipes, `index` for cookbook
+/// indexes and `tag` for pages listing the recipes with a tag. Templates loaded from a directory
+/// or theme are matched by file stem, so `recipe.html` is used as the `recipe` template. Other
+/// templates (such as base layouts or partials) may be used with Tera's `extends` and `include`.
+///
+/// Every template has access to a `root` variable, the relative path from the rendered page to
+/// the output directory, and to any variables added with [TemplateRenderer::with_var] or
+/// [TemplateRenderer::with_context_file]. The `labels` variable holds the [Labels](crate::Labels)
+/// of the renderer's [Locale], set with [TemplateRenderer::with_locale], and `locale` its language
+/// code. Recipe templates also receive `original_servings`, the serving count before any override
+/// was applied, and `extra`, the recipe's [extra fields](crate::Metadata::extra).
+#[derive(Clone, Debug)]
+pub struct TemplateRenderer {
+    env: Tera,
+    vars: Context,
+    locale: Locale,
+    /// Optionally override the serving count when outputting.
+    pub servings: Option<u32>,
+    /// Optionally convert ingredient amounts into a [UnitSystem].
+    pub units: Option<UnitSystem>,
+    /// Optionally render translated text in this language instead of each recipe's default
+    /// language.
+    pub language: Option<String>,
+    /// Optionally override the file extension of rendered output.
+    ///
+    /// By default, the extension is taken from the recipe template's file name, ignoring any
+    /// trailing `.tera` (e.g. `recipe.html.tera` renders `html`), falling back to `txt`.
+    pub extension: Option<String>,
+}
+
+impl Renderer for TemplateRenderer {
+    fn render(&self, recipe: &Recipe) -> Result<String, SousError> {
+        let name = theme::find(&self.env, "recipe").unwrap_or("recipe");
+
+        let original_servings = recipe.metadata.servings;
+        let recipe = prepare(recipe, self.language.as_deref(), self.servings, self.units);
+
+        let mut ctx = self.context("");
+        ctx.extend(theme::recipe_context(&recipe)?);
+        ctx.insert("original_servings", &original_servings);
+        Ok(self.env.render(name, &ctx)?)
+    }
+
+    fn extension(&self) -> &str {
+        if let Some(extension) = &self.extension {
+            return extension;
+        }
+
+        theme::find(&self.env, "recipe")
+            .and_then(|name| name.strip_suffix(".tera").unwrap_or(name).rsplit_once('.'))
+            .map(|(_, extension)| extension)
+            .unwrap_or("txt")
+    }
+
+    fn render_index(&self, index: &Index) -> Result<Option<String>, SousError> {
+        let Some(name) = theme::find(&self.env, "index") else {
+            return Ok(None);
+        };
+
+        let mut ctx = self.context("");
+        ctx.extend(Context::from_serialize(index)?);
+        if let Some(title) = &index.title {
+            ctx.insert("site_title", title);
+        }
+        let sections: Vec<IndexSection> = index
+            .sections
+            .iter()
+            .map(|section| IndexSection {
+                title: section.title.clone(),
+                entries: self.entries(&section.entries),
+            })
+            .collect();
+        ctx.insert("sections", &sections);
+        Ok(Some(self.env.render(name, &ctx)?))
+    }
+
+    fn render_tag(&self, tag: &IndexSection) -> Result<Option<String>, SousError> {
+        let Some(name) = theme::find(&self.env, "tag") else {
+            return Ok(None);
+        };
+
+        let mut ctx = self.context("../");
+        ctx.insert("title", &tag.title);
+        ctx.insert("entries", &self.entries(&tag.entries));
+        Ok(Some(self.env.render(name, &ctx)?))
+    }
+}
+
+impl TemplateRenderer {
+    /// Create a new renderer using the provided template file.
+    pub fn from_path(path: &Path) -> Result<Self, SousError> {
+        let mut env = theme::environment();
+
+        env.add_template_file(path, Some("recipe"))?;
+
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let extension = Path::new(name.strip_suffix(".tera").unwrap_or(&name))
+            .extension()
+            .map(|extension| extension.to_string_lossy().into_owned());
+
+        Ok(TemplateRenderer {
+            extension,
+            ..TemplateRenderer::with_env(env)
+        })
+    }
+
+    /// Create a new renderer using the provided raw string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str<S: AsRef<str>>(template: S) -> Result<Self, SousError> {
+        let mut env = theme::environment();
+
+        env.add_raw_template("recipe", template.as_ref())?;
+
+        Ok(TemplateRenderer::with_env(env))
+    }
+
+    /// Create a new renderer using every template in the provided directory.
+    pub fn from_dir(dir: &Path) -> Result<Self, SousError> {
+        TemplateRenderer::with_env(theme::environment()).with_theme(dir)
+    }
+
+    /// Create a new renderer using one of the themes built into sous, either `markdown` or
+    /// `html`.
+    pub fn builtin(name: &str) -> Result<Self, SousError> {
+        let mut env = theme::environment();
+
+        env.add_raw_templates(theme::builtin(name)?.to_vec())?;
+
+        Ok(TemplateRenderer::with_env(env))
+    }
+
+    /// Add every template in the provided directory, replacing existing templates of the same
+    /// name. This allows overriding single files of a built-in theme.
+    ///
+    /// Only `.html`, `.md` and `.tera` files are loaded as templates; other files are ignored.
+    pub fn with_theme(mut self, dir: &Path) -> Result<Self, SousError> {
+        theme::add_dir(&mut self.env, dir)?;
+
+        Ok(self)
+    }
+
+    /// Make a variable available to every template.
+    pub fn with_var<T: Serialize + ?Sized>(mut self, key: &str, value: &T) -> Self {
+        self.vars.insert(key, value);
+
+        self
+    }
+
+    /// Localize labels and the numbers formatted by filters such as `fraction` into the provided
+    /// [Locale].
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        filters::register(&mut self.env, locale);
+        self.locale = locale;
+
+        self
+    }
+
+    /// Make every key of the provided YAML (or JSON) mapping available to every template as a
+    /// variable.
+    pub fn with_context_file(mut self, path: &Path) -> Result<Self, SousError> {
+        let value: serde_json::Value = serde_yaml::from_str(&fs::read_to_string(path)?)?;
+        self.vars.extend(Context::from_value(value)?);
+
+        Ok(self)
+    }
+
+    /// Use the provided template file for rendering cookbook indexes.
+    pub fn with_index_path(mut self, path: &Path) -> Result<Self, SousError> {
+        self.env.add_template_file(path, Some("index"))?;
+
+        Ok(self)
+    }
+
+    /// Use the provided raw string for rendering cookbook indexes.
+    pub fn with_index_str<S: AsRef<str>>(mut self, template: S) -> Result<Self, SousError> {
+        self.env.add_raw_template("index", template.as_ref())?;
+
+        Ok(self)
+    }
+
+    fn with_env(env: Tera) -> Self {
+        TemplateRenderer {
+            env,
+            vars: Context::new(),
+            locale: Locale::default(),
+            language: None,
+            servings: None,
+            units: None,
+            extension: None,
+        }
+    }
+
+    fn context(&self, root: &str) -> Context {
+        let mut ctx = Context::new();
+        ctx.insert("labels", &self.locale.labels());
+        ctx.insert("locale", &self.locale);
+        ctx.extend(self.vars.clone());
+        ctx.insert("root", root);
+        ctx
+    }
+
+    /// Apply the serving count override to index entries.
+    fn entries(&self, entries: &[IndexEntry]) -> Vec<IndexEntry> {
+        entries
+            .iter()
+            .map(|entry| IndexEntry {
+                servings: self.servings.unwrap_or(entry.servings),
+                ..entry.clone()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::render::tests::{gen_index, gen_recipe};
+    use crate::tests::temp_dir;
+
+    #[test]
+    fn test_template_render_metadata() {
+        let recipe = gen_recipe();
+        let template =
+            "{{ name }}\n{{ author }}\n{{ servings }} serving \n{{ cook_minutes }} minute";
+
+        let renderer = TemplateRenderer::from_str(template).unwrap();
+        let output = renderer.render(&recipe).unwrap();
+
+        assert!(output.contains("test recipe"));
+        assert!(output.contains("test author"));
+        assert!(output.contains("1 serving"));
+        assert!(output.contains("1 minute"));
+    }
+
+    #[test]
+    fn test_template_render_labels() {
+        let mut recipe = gen_recipe();
+        recipe.metadata.tags = vec!["quick".to_string()];
+        recipe.metadata.cuisine = Some("italian".to_string());
+        let template = "{% for tag in tags %}#{{ tag }} {% endfor %}{{ cuisine }}";
+
+        let renderer = TemplateRenderer::from_str(template).unwrap();
+        let output = renderer.render(&recipe).unwrap();
+
+        assert_eq!(output, "#quick italian");
+    }
+
+    #[test]
+    fn test_template_render_extra() {
+        let mut recipe = gen_recipe();
+        recipe.metadata.extra.insert("rating".to_string(), 5.into());
+        recipe
+            .metadata
+            .extra
+            .insert("root".to_string(), "elsewhere".into());
+        let template = "{{ root }}|{{ extra.rating }}{% for key, value in extra %} {{ key }}={{ value }}{% endfor %}";
+
+        let renderer = TemplateRenderer::from_str(template).unwrap();
+        let output = renderer.render(&recipe).unwrap();
+
+        assert_eq!(output, "|5 rating=5 root=elsewhere");
+    }
+
+    #[test]
+    fn test_template_render_ingredients() {
+        let recipe = gen_recipe();
+        let template = "{% for ingredient in ingredients %}\n{{ ingredient.name }}\n{% endfor %}";
+
+        let renderer = TemplateRenderer::from_str(template).unwrap();
+        let output = renderer.render(&recipe).unwrap();
+
+        assert!(output.contains("test ingredient"));
+    }
+
+    #[test]
+    fn test_template_render_steps() {
+        let recipe = gen_recipe();
+        let template = "{% for step in steps %}\n{{ step }}\n{% endfor %}";
+
+        let renderer = TemplateRenderer::from_str(template).unwrap();
+        let output = renderer.render(&recipe).unwrap();
+
+        assert!(output.contains("Step one"));
+    }
+
+    #[test]
+    fn test_template_render_servings() {
+        let mut renderer = TemplateRenderer::from_str(
+            "{{ servings }}/{{ original_servings }}: {{ ingredients.0.amount }}",
+        )
+        .unwrap();
+        renderer.servings = Some(3);
+
+        assert_eq!(renderer.render(&gen_recipe()).unwrap(), "3/1: 3");
+    }
+
+    #[test]
+    fn test_template_render_vars() {
+        let dir = temp_dir("template-context");
+        let path = dir.join("context.yml");
+        fs::write(&path, "site: My Kitchen\nname: ignored").unwrap();
+
+        let renderer = TemplateRenderer::from_str("{{ site }} | {{ date }} | {{ name }}")
+            .unwrap()
+            .with_var("date", "2024-01-01")
+            .with_context_file(&path)
+            .unwrap();
+
+        assert_eq!(
+            renderer.render(&gen_recipe()).unwrap(),
+            "My Kitchen | 2024-01-01 | test recipe"
+        );
+    }
+
+    #[test]
+    fn test_template_render_index() {
+        let index = gen_index();
+        let template = "{% for section in sections %}{% for entry in section.entries %}\n{{ entry.name }}: {{ entry.link }}\n{% endfor %}{% endfor %}";
+
+        let renderer = TemplateRenderer::from_str("")
+            .unwrap()
+            .with_index_str(template)
+            .unwrap();
+        let output = renderer.render_index(&index).unwrap().unwrap();
+
+        assert!(output.contains("test recipe: test.md"));
+    }
+
+    #[test]
+    fn test_template_render_no_index() {
+        let renderer = TemplateRenderer::from_str("").unwrap();
+
+        assert_eq!(renderer.render_index(&gen_index()).unwrap(), None);
+        assert_eq!(renderer.render_tag(&gen_index().sections[0]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_template_render_builtin() {
+        let mut recipe = gen_recipe();
+        recipe.metadata.tags = vec!["Quick Meals".to_string()];
+        recipe.ingredients[0].recipe = Some("sub-recipe.yml".to_string());
+
+        let renderer = TemplateRenderer::builtin("markdown").unwrap();
+        let md = renderer.render(&recipe).unwrap();
+        let index = renderer.render_index(&gen_index()).unwrap().unwrap();
+        let tag = renderer
+            .render_tag(&gen_index().sections[0])
+            .unwrap()
+            .unwrap();
+
+        assert!(md.contains("# test recipe\n**test author**\n**1 servings | 1 minutes cook time**"));
+        assert!(md.contains("Tags: [Quick Meals](tags/quick-meals.md)"));
+        assert!(md.contains("* 1 [test ingredient](sub-recipe.md)"));
+        assert!(md.contains("## Method\n1. Step one"));
+        assert!(index.contains("## test section\n* [test recipe](test.md) | 1 servings"));
+        assert!(tag.contains("# test section\n\n* [test recipe](../test.md)"));
+
+        assert!(TemplateRenderer::builtin("missing").is_err());
+    }
+
+    #[test]
+    fn test_template_extension() {
+        let dir = temp_dir("template-extension");
+        let path = dir.join("template.html.tera");
+        fs::write(&path, "{{ name }}").unwrap();
+
+        let mut renderer = TemplateRenderer::from_path(&path).unwrap();
+        assert_eq!(renderer.extension(), "html");
+        assert_eq!(renderer.mime_type(), "text/html");
+        renderer.extension = Some("json".to_string());
+        assert_eq!(renderer.extension(), "json");
+
+        assert_eq!(TemplateRenderer::from_str("").unwrap().extension(), "txt");
+        assert_eq!(
+            TemplateRenderer::builtin("markdown").unwrap().extension(),
+            "md"
+        );
+    }
+
+    #[test]
+    fn test_template_render_dir() {
+        let dir = temp_dir("template-dir");
+        fs::create_dir_all(dir.join("partials")).unwrap();
+        fs::write(
+            dir.join("base.txt.tera"),
+            "[{% block body %}{% endblock %}]",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("recipe.txt.tera"),
+            "{% extends \"base.txt.tera\" %}{% block body %}{% include \"partials/name.txt.tera\" %}{% endblock %}",
+        )
+        .unwrap();
+        fs::write(dir.join("partials").join("name.txt.tera"), "{{ name }}").unwrap();
+        fs::write(dir.join("notes.txt"), "{{").unwrap();
+
+        let renderer = TemplateRenderer::from_dir(&dir).unwrap();
+        assert_eq!(renderer.render(&gen_recipe()).unwrap(), "[test recipe]");
+        assert_eq!(renderer.extension(), "txt");
+        assert_eq!(renderer.render_index(&gen_index()).unwrap(), None);
+
+        let overrides = dir.join("overrides");
+        fs::create_dir_all(overrides.join("partials")).unwrap();
+        fs::write(
+            overrides.join("partials").join("entry.md"),
+            "- {{ entry.name }}",
+        )
+        .unwrap();
+
+        let renderer = TemplateRenderer::builtin("markdown")
+            .unwrap()
+            .with_theme(&overrides)
+            .unwrap();
+        let index = renderer.render_index(&gen_index()).unwrap().unwrap();
+        assert!(index.contains("## test section\n- test recipe"));
+    }
+}
@@ -0,0 +1,312 @@
+//! Renderer for plain-text output, optionally styled for terminals.
+
+use std::fmt::Write;
+
+use crate::format::{duration, fraction};
+use crate::render::{prepare, Renderer};
+use crate::units::pluralize_unit;
+use crate::{Index, Labels, Recipe, SousError, UnitSystem};
+
+/// Width used when no explicit width is set.
+const DEFAULT_WIDTH: usize = 80;
+
+/// Narrowest column wrapped text is allowed to shrink to.
+const MIN_COLUMN: usize = 20;
+
+/// ANSI SGR codes used when styling is enabled.
+const BOLD: &str = "1";
+const DIM: &str = "2";
+const HEADING: &str = "1;36";
+
+/// Renders recipes as plain text for reading in a terminal.
+///
+/// Text is word-wrapped at [TextRenderer::width] columns and ingredient amounts, units and names
+/// are aligned in columns. With [TextRenderer::color] set, names and headings are highlighted with
+/// ANSI escape codes.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct TextRenderer {
+    /// Column at which to wrap lines, defaulting to 80.
+    pub width: Option<usize>,
+    /// Whether to style output with ANSI colors and bold text.
+    pub color: bool,
+    /// Whether to output meta information.
+    pub skip_meta: bool,
+    /// Whether to output ingredient list.
+    pub skip_ingredients: bool,
+    /// Whether to output the procedure.
+    pub skip_steps: bool,
+    /// Optionally override the serving count when outputting.
+    pub servings: Option<u32>,
+    /// Optionally convert ingredient amounts into a [UnitSystem].
+    pub units: Option<UnitSystem>,
+    /// Optionally render translated text in this language instead of each recipe's default
+    /// language.
+    pub language: Option<String>,
+    /// Text used for headings and the serving count.
+    pub labels: Labels,
+}
+
+impl TextRenderer {
+    /// Create default render self.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn width(&self) -> usize {
+        self.width.unwrap_or(DEFAULT_WIDTH)
+    }
+
+    /// Wrap `text` in the given SGR style if color is enabled.
+    fn style(&self, text: &str, code: &str) -> String {
+        if self.color {
+            format!("\x1b[{code}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Write `text` wrapped to the renderer's width, starting with `prefix` and indenting
+    /// continuation lines to line up with the first.
+    fn paragraph(&self, output: &mut String, prefix: &str, text: &str) {
+        let indent = prefix.chars().count();
+        let width = self.width().saturating_sub(indent).max(MIN_COLUMN);
+
+        for (i, line) in wrap(text, width).iter().enumerate() {
+            if i == 0 {
+                writeln!(output, "{prefix}{line}").unwrap();
+            } else {
+                writeln!(output, "{:indent$}{line}", "").unwrap();
+            }
+        }
+    }
+}
+
+impl Renderer for TextRenderer {
+    fn render(&self, recipe: &Recipe) -> Result<String, SousError> {
+        let mut output = String::new();
+
+        let recipe = prepare(recipe, self.language.as_deref(), self.servings, self.units);
+        let metadata = &recipe.metadata;
+
+        for line in wrap(metadata.name.as_str(), self.width()) {
+            writeln!(output, "{}", self.style(&line, BOLD)).unwrap();
+        }
+
+        if !self.skip_meta {
+            let mut byline = format!("{} {}", self.labels.by, metadata.author);
+            if let Some(url) = &metadata.url {
+                write!(byline, " | {}", url).unwrap();
+            }
+            for line in wrap(&byline, self.width()) {
+                writeln!(output, "{}", self.style(&line, DIM)).unwrap();
+            }
+
+            let mut times = vec![format!("{} {}", metadata.servings, self.labels.servings)];
+            if let Some(prep) = metadata.prep_minutes {
+                times.push(format!("{prep} {}", self.labels.prep));
+            }
+            times.push(format!("{} {}", metadata.cook_minutes, self.labels.cook));
+            self.paragraph(&mut output, "", &times.join(" | "));
+        }
+
+        if !self.skip_ingredients && !recipe.ingredients.is_empty() {
+            writeln!(
+                output,
+                "\n{}",
+                self.style(&self.labels.ingredients, HEADING)
+            )
+            .unwrap();
+
+            let rows: Vec<(String, String, &str)> = recipe
+                .ingredients
+                .iter()
+                .map(|ingredient| {
+                    let amount = ingredient.amount.map(fraction).unwrap_or_default();
+                    let unit = ingredient
+                        .unit
+                        .as_ref()
+                        .map(|unit| pluralize_unit(unit, ingredient.amount.unwrap_or(1.0)))
+                        .unwrap_or_default();
+                    (amount, unit, ingredient.name.as_str())
+                })
+                .collect();
+            let amount_width = rows.iter().map(|r| r.0.chars().count()).max().unwrap_or(0);
+            let unit_width = rows.iter().map(|r| r.1.chars().count()).max().unwrap_or(0);
+
+            for (amount, unit, name) in rows.iter() {
+                let mut prefix = String::from("  ");
+                if amount_width > 0 {
+                    write!(prefix, "{amount:>amount_width$} ").unwrap();
+                }
+                if unit_width > 0 {
+                    write!(prefix, "{unit:<unit_width$} ").unwrap();
+                }
+                if amount_width + unit_width > 0 {
+                    prefix.push(' ');
+                }
+                self.paragraph(&mut output, &prefix, name);
+            }
+        }
+
+        if !self.skip_steps && !recipe.steps.is_empty() {
+            writeln!(output, "\n{}", self.style(&self.labels.method, HEADING)).unwrap();
+
+            let number_width = recipe.steps.len().to_string().len();
+            for (i, step) in recipe.steps.iter().enumerate() {
+                let prefix = format!("  {:>number_width$}. ", i + 1);
+                self.paragraph(&mut output, &prefix, step.as_str());
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn render_index(&self, index: &Index) -> Result<Option<String>, SousError> {
+        let mut output = String::new();
+
+        let title = index.title.as_ref().unwrap_or(&self.labels.index);
+        writeln!(output, "{}", self.style(title, BOLD)).unwrap();
+        if let Some(author) = &index.author {
+            let byline = format!("{} {author}", self.labels.by);
+            writeln!(output, "{}", self.style(&byline, DIM)).unwrap();
+        }
+        if let Some(description) = &index.description {
+            output.push('\n');
+            self.paragraph(&mut output, "", description);
+        }
+
+        for section in index.sections.iter() {
+            writeln!(output, "\n{}", self.style(&section.title, HEADING)).unwrap();
+
+            let name_width = section
+                .entries
+                .iter()
+                .map(|entry| entry.name.chars().count())
+                .max()
+                .unwrap_or(0);
+            for entry in section.entries.iter() {
+                writeln!(
+                    output,
+                    "  {:<name_width$}  {} {} | {}",
+                    entry.name,
+                    self.servings.unwrap_or(entry.servings),
+                    self.labels.servings,
+                    duration(entry.total_minutes)
+                )
+                .unwrap();
+            }
+        }
+
+        Ok(Some(output))
+    }
+}
+
+/// Greedily wrap `text` into lines of at most `width` characters.
+///
+/// Words longer than `width` are placed on a line of their own.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::tests::{gen_index, gen_recipe};
+    use crate::{Ingredient, Locale};
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            wrap("the quick brown fox jumps", 10),
+            vec!["the quick", "brown fox", "jumps"]
+        );
+        assert_eq!(wrap("", 10), vec![""]);
+    }
+
+    #[test]
+    fn test_text_render() {
+        let recipe = gen_recipe();
+
+        let renderer = TextRenderer::new();
+        let text = renderer.render(&recipe).unwrap();
+
+        assert_eq!(
+            text,
+            "test recipe\nby test author\n1 servings | 1 minutes cook time\n\n\
+             Ingredients\n  1  test ingredient\n\nMethod\n  1. Step one\n"
+        );
+    }
+
+    #[test]
+    fn test_text_render_aligned() {
+        let mut recipe = gen_recipe();
+        recipe.ingredients.push(Ingredient {
+            name: "flour".into(),
+            amount: Some(1.5),
+            unit: Some("cup".to_string()),
+            ..Default::default()
+        });
+        recipe.steps = vec!["Mix the test ingredient with the flour until smooth".into()];
+
+        let renderer = TextRenderer {
+            width: Some(30),
+            skip_meta: true,
+            ..Default::default()
+        };
+        let text = renderer.render(&recipe).unwrap();
+
+        assert!(text.contains("      1       test ingredient\n  1 1/2 cups  flour\n"));
+        assert!(text.contains("  1. Mix the test ingredient\n     with the flour until\n"));
+    }
+
+    #[test]
+    fn test_text_render_color() {
+        let renderer = TextRenderer {
+            color: true,
+            ..Default::default()
+        };
+        let text = renderer.render(&gen_recipe()).unwrap();
+
+        assert!(text.starts_with("\x1b[1mtest recipe\x1b[0m\n"));
+        assert!(text.contains("\x1b[1;36mIngredients\x1b[0m"));
+    }
+
+    #[test]
+    fn test_text_render_labels() {
+        let renderer = TextRenderer {
+            labels: Locale::German.labels(),
+            ..Default::default()
+        };
+        let text = renderer.render(&gen_recipe()).unwrap();
+
+        assert!(text.starts_with("test recipe\nvon test author\n"));
+        assert!(text.contains("1 Portionen | 1 Minuten Kochzeit\n"));
+        assert!(text.contains("\nZutaten\n"));
+        assert!(text.contains("\nZubereitung\n"));
+    }
+
+    #[test]
+    fn test_text_render_index() {
+        let renderer = TextRenderer::new();
+        let text = renderer.render_index(&gen_index()).unwrap().unwrap();
+
+        assert!(text.starts_with("test cookbook\n"));
+        assert!(text.contains("test section\n  test recipe  1 servings | 1 min\n"));
+    }
+}
@@ -0,0 +1,174 @@
+//! Built-in template themes and helpers for loading theme directories.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tera::{Context, Tera};
+
+use crate::render::filters;
+use crate::{Locale, Recipe, SousError};
+
+/// Extensions of files in a theme directory loaded as templates. Other files are static assets.
+const TEMPLATE_EXTENSIONS: &[&str] = &["html", "md", "tera"];
+
+/// Templates making up a theme, as pairs of template name and content.
+pub(crate) type Theme = &'static [(&'static str, &'static str)];
+
+/// Built-in theme rendering HTML pages.
+pub(crate) const HTML_THEME: Theme = &[
+    ("base.html", include_str!("../themes/html/base.html")),
+    ("recipe.html", include_str!("../themes/html/recipe.html")),
+    ("index.html", include_str!("../themes/html/index.html")),
+    ("tag.html", include_str!("../themes/html/tag.html")),
+    ("style.css", include_str!("../themes/html/style.css")),
+];
+
+/// Built-in theme rendering Markdown documents.
+pub(crate) const MARKDOWN_THEME: Theme = &[
+    (
+        "partials/entry.md",
+        include_str!("../themes/markdown/partials/entry.md"),
+    ),
+    ("recipe.md", include_str!("../themes/markdown/recipe.md")),
+    ("index.md", include_str!("../themes/markdown/index.md")),
+    ("tag.md", include_str!("../themes/markdown/tag.md")),
+];
+
+/// Look up a built-in theme by name.
+pub(crate) fn builtin(name: &str) -> Result<Theme, SousError> {
+    match name {
+        "html" => Ok(HTML_THEME),
+        "markdown" => Ok(MARKDOWN_THEME),
+        _ => Err(SousError::UnknownTheme(name.to_string())),
+    }
+}
+
+/// Create a template environment with the filters available to every theme.
+pub(crate) fn environment() -> Tera {
+    let mut env = Tera::default();
+    filters::register(&mut env, Locale::default());
+    env
+}
+
+/// Create the template context of a recipe, holding its fields as variables of their own.
+///
+/// [Extra fields](crate::Metadata::extra) are only available through the `extra` variable, so
+/// they can never replace a recipe field or a variable set by the renderer, such as `root`.
+pub(crate) fn recipe_context(recipe: &Recipe) -> Result<Context, SousError> {
+    let mut known = recipe.clone();
+    let extra = std::mem::take(&mut known.metadata.extra);
+
+    let mut ctx = Context::from_serialize(&known)?;
+    ctx.insert("extra", &extra);
+    Ok(ctx)
+}
+
+/// Add every template below a directory to the environment, named by its path relative to the
+/// directory (e.g. `partials/entry.md`). Templates already present with the same name are
+/// replaced, so a directory can override a built-in theme one file at a time.
+///
+/// Only `.html`, `.md` and `.tera` files are templates. Every other file is returned as a static
+/// asset, paired with its relative path, for the caller to copy alongside the rendered output.
+pub(crate) fn add_dir(env: &mut Tera, dir: &Path) -> Result<Vec<(String, PathBuf)>, SousError> {
+    let mut templates = Vec::new();
+    let mut assets = Vec::new();
+    collect(dir, "", &mut templates, &mut assets)?;
+    env.add_raw_templates(templates)?;
+    Ok(assets)
+}
+
+/// Find the name of the top-level template whose file stem is `stem`, ignoring any trailing
+/// `.tera` (e.g. `recipe` matches `recipe.html` and `recipe.html.tera`). A template named exactly
+/// `stem` is preferred.
+pub(crate) fn find<'a>(env: &'a Tera, stem: &str) -> Option<&'a str> {
+    env.get_template_names()
+        .filter(|name| {
+            let file = name.strip_suffix(".tera").unwrap_or(name);
+            *name == stem
+                || (!name.contains('/') && Path::new(file).file_stem() == Some(stem.as_ref()))
+        })
+        .min_by_key(|name| (*name != stem, *name))
+}
+
+fn collect(
+    dir: &Path,
+    prefix: &str,
+    templates: &mut Vec<(String, String)>,
+    assets: &mut Vec<(String, PathBuf)>,
+) -> Result<(), SousError> {
+    let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if file_name.starts_with('.') {
+            continue;
+        }
+
+        let name = format!("{prefix}{file_name}");
+        if entry.file_type()?.is_dir() {
+            collect(&entry.path(), &format!("{name}/"), templates, assets)?;
+        } else if is_template(&file_name) {
+            templates.push((name, fs::read_to_string(entry.path())?));
+        } else {
+            assets.push((name, entry.path()));
+        }
+    }
+
+    Ok(())
+}
+
+fn is_template(file_name: &str) -> bool {
+    Path::new(file_name)
+        .extension()
+        .is_some_and(|extension| TEMPLATE_EXTENSIONS.contains(&&*extension.to_string_lossy()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::tests::temp_dir;
+
+    #[test]
+    fn test_find() {
+        let mut env = environment();
+        env.add_raw_templates(MARKDOWN_THEME.to_vec()).unwrap();
+
+        assert_eq!(find(&env, "recipe"), Some("recipe.md"));
+        assert_eq!(find(&env, "entry"), None);
+        assert_eq!(find(&env, "missing"), None);
+
+        let mut env = environment();
+        env.add_raw_template("recipe.html.tera", "").unwrap();
+        assert_eq!(find(&env, "recipe"), Some("recipe.html.tera"));
+    }
+
+    #[test]
+    fn test_add_dir() {
+        let dir = temp_dir("theme");
+        fs::create_dir_all(dir.join("partials")).unwrap();
+        fs::write(dir.join("partials").join("entry.md"), "* {{ entry.name }}").unwrap();
+        fs::write(dir.join(".hidden"), "{{").unwrap();
+        fs::write(dir.join("logo.svg"), "<svg>{{</svg>").unwrap();
+
+        let mut env = environment();
+        env.add_raw_templates(MARKDOWN_THEME.to_vec()).unwrap();
+        let assets = add_dir(&mut env, &dir).unwrap();
+
+        assert_eq!(assets, vec![("logo.svg".to_string(), dir.join("logo.svg"))]);
+        assert!(!env.get_template_names().any(|name| name == "logo.svg"));
+
+        assert!(env
+            .get_template_names()
+            .any(|name| name == "partials/entry.md"));
+        assert!(!env.get_template_names().any(|name| name == ".hidden"));
+
+        let mut ctx = tera::Context::new();
+        ctx.insert("title", "tag");
+        ctx.insert("root", "");
+        ctx.insert("entries", &[HashMap::from([("name", "pie")])]);
+        assert_eq!(env.render("tag.md", &ctx).unwrap(), "# tag\n\n* pie\n");
+    }
+}
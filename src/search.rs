@@ -0,0 +1,540 @@
+//! Types for searching collections of recipes.
+
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Filter, Metadata, Recipe, SousError, DEFAULT_LANGUAGE};
+
+/// The searchable parts of a recipe stored in a cookbook.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Summary {
+    /// File name of the recipe within its cookbook.
+    pub file: String,
+    /// Recipe [Metadata].
+    pub metadata: Metadata,
+    /// Names of the recipe's ingredients, in every available language.
+    pub ingredients: Vec<String>,
+}
+
+impl Summary {
+    /// Summarize the provided [Recipe], stored as `file`.
+    pub fn new(file: &str, recipe: &Recipe) -> Self {
+        Summary {
+            file: file.to_string(),
+            metadata: recipe.metadata.clone(),
+            ingredients: recipe
+                .ingredients
+                .iter()
+                .flat_map(|i| i.name.values())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// Recipe time that can be limited in a [Query].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeField {
+    /// Prep time.
+    Prep,
+    /// Cook time.
+    Cook,
+    /// Prep and cook time combined.
+    Total,
+}
+
+/// Comparison used by a [TimeLimit].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    /// Strictly less than.
+    Less,
+    /// Less than or equal to.
+    LessEqual,
+    /// Strictly greater than.
+    Greater,
+    /// Greater than or equal to.
+    GreaterEqual,
+    /// Exactly equal to.
+    Equal,
+}
+
+/// Restriction on one of a recipe's times, e.g. `total < 30m`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeLimit {
+    /// The time being limited.
+    pub field: TimeField,
+    /// How the recipe's time is compared against the limit.
+    pub comparison: Comparison,
+    /// The limit in minutes.
+    pub minutes: u32,
+}
+
+impl TimeLimit {
+    /// Check whether the provided [Metadata] satisfies the limit.
+    pub fn matches(&self, metadata: &Metadata) -> bool {
+        let minutes = match self.field {
+            TimeField::Prep => metadata.prep_minutes.unwrap_or(0),
+            TimeField::Cook => metadata.cook_minutes,
+            TimeField::Total => metadata.total_minutes(),
+        };
+
+        match self.comparison {
+            Comparison::Less => minutes < self.minutes,
+            Comparison::LessEqual => minutes <= self.minutes,
+            Comparison::Greater => minutes > self.minutes,
+            Comparison::GreaterEqual => minutes >= self.minutes,
+            Comparison::Equal => minutes == self.minutes,
+        }
+    }
+}
+
+/// A parsed recipe search query.
+///
+/// Queries are made of whitespace-separated terms. Plain terms are matched against every field,
+/// while prefixed terms only match a single field:
+///
+/// * `name:`, `author:` - the recipe's name or author contains the value
+/// * `has:`, `not:` - an ingredient does or does not contain the value
+/// * `tag:`, `cuisine:`, `course:`, `diet:` - the recipe carries the label
+/// * `total`, `prep`, `cook` followed by `<`, `<=`, `>`, `>=` or `=` and a duration such as `30m`
+///   or `1h` - the recipe's time is within the limit
+///
+/// Values containing spaces may be quoted, e.g. `has:"olive oil"`. Every term must match for a
+/// recipe to be included in the results.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Query {
+    /// Terms matched against any field.
+    pub terms: Vec<String>,
+    /// Terms the recipe name must contain.
+    pub name: Vec<String>,
+    /// Terms the recipe author must contain.
+    pub author: Vec<String>,
+    /// Terms an ingredient must contain.
+    pub has: Vec<String>,
+    /// Terms no ingredient may contain.
+    pub not: Vec<String>,
+    /// Labels the recipe must carry.
+    pub filter: Filter,
+    /// Limits on the recipe's times.
+    pub limits: Vec<TimeLimit>,
+    /// Language to name matching recipes in, defaulting to each recipe's own language.
+    pub language: Option<String>,
+}
+
+/// A recipe matching a [Query].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SearchResult {
+    /// File name of the recipe within its cookbook.
+    pub file: String,
+    /// Display name of the recipe.
+    pub name: String,
+    /// Original author of the recipe.
+    pub author: String,
+    /// Relevance of the recipe to the query; higher is better.
+    pub score: u32,
+    /// Descriptions of the fields that matched the query.
+    pub snippets: Vec<String>,
+}
+
+const NAME_SCORE: u32 = 10;
+const LABEL_SCORE: u32 = 5;
+const INGREDIENT_SCORE: u32 = 4;
+const AUTHOR_SCORE: u32 = 3;
+
+impl Query {
+    /// Create a new, empty query matching every recipe.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Parse a query from the provided string slice.
+    pub fn parse(query: &str) -> Result<Query, SousError> {
+        let mut parsed = Query::new();
+        let mut tokens = tokenize(query).into_iter().peekable();
+
+        while let Some(token) = tokens.next() {
+            if let Some((field, mut rest)) = split_comparison(&token, tokens.peek()) {
+                if rest.is_empty() {
+                    rest = tokens.next().unwrap_or_default();
+                }
+                if rest.trim_start_matches(['<', '>', '=']).is_empty() {
+                    rest.push_str(&tokens.next().unwrap_or_default());
+                }
+                parsed.limits.push(parse_limit(&field, &rest)?);
+                continue;
+            }
+
+            let Some((key, value)) = token.split_once(':') else {
+                parsed.terms.push(token.to_lowercase());
+                continue;
+            };
+            let value = if value.is_empty() {
+                tokens.next().unwrap_or_default()
+            } else {
+                value.to_string()
+            }
+            .to_lowercase();
+            if value.is_empty() {
+                return Err(SousError::QueryError(format!("missing value for '{key}:'")));
+            }
+
+            match key.to_lowercase().as_str() {
+                "name" => parsed.name.push(value),
+                "author" => parsed.author.push(value),
+                "has" => parsed.has.push(value),
+                "not" => parsed.not.push(value),
+                "tag" => parsed.filter.tags.push(value),
+                "cuisine" => parsed.filter.cuisine = Some(value),
+                "course" => parsed.filter.course = Some(value),
+                "diet" => parsed.filter.dietary.push(value),
+                _ => return Err(SousError::QueryError(format!("unknown field '{key}:'"))),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Check the provided [Summary] against the query.
+    ///
+    /// Returns [None] if the recipe does not match.
+    pub fn search(&self, summary: &Summary) -> Option<SearchResult> {
+        let metadata = &summary.metadata;
+        if !self.filter.matches(metadata) || !self.limits.iter().all(|l| l.matches(metadata)) {
+            return None;
+        }
+
+        let mut score = 0;
+        let mut snippets: Vec<String> = Vec::new();
+        let mut hit = |points: u32, snippet: String| {
+            score += points;
+            if !snippets.contains(&snippet) {
+                snippets.push(snippet);
+            }
+        };
+
+        let name = metadata.name.values().join(" ").to_lowercase();
+        let author = metadata.author.to_lowercase();
+        let ingredients: Vec<String> = summary
+            .ingredients
+            .iter()
+            .map(|i| i.to_lowercase())
+            .collect();
+        let labels: Vec<&String> = metadata
+            .tags
+            .iter()
+            .chain(metadata.dietary.iter())
+            .chain(metadata.cuisine.iter())
+            .chain(metadata.course.iter())
+            .collect();
+
+        for term in self.not.iter() {
+            if ingredients.iter().any(|i| i.contains(term)) {
+                return None;
+            }
+        }
+
+        for term in self.name.iter() {
+            if !name.contains(term) {
+                return None;
+            }
+            hit(NAME_SCORE, format!("name: {}", metadata.name));
+        }
+
+        for term in self.author.iter() {
+            if !author.contains(term) {
+                return None;
+            }
+            hit(AUTHOR_SCORE, format!("author: {}", metadata.author));
+        }
+
+        for term in self.has.iter() {
+            let found = ingredients.iter().position(|i| i.contains(term))?;
+            hit(
+                INGREDIENT_SCORE,
+                format!("ingredient: {}", summary.ingredients[found]),
+            );
+        }
+
+        for term in self.terms.iter() {
+            let mut found = false;
+
+            if name.contains(term) {
+                found = true;
+                hit(NAME_SCORE, format!("name: {}", metadata.name));
+            }
+            for label in labels.iter() {
+                if label.to_lowercase().contains(term) {
+                    found = true;
+                    hit(LABEL_SCORE, format!("label: {label}"));
+                }
+            }
+            for (i, ingredient) in ingredients.iter().enumerate() {
+                if ingredient.contains(term) {
+                    found = true;
+                    hit(
+                        INGREDIENT_SCORE,
+                        format!("ingredient: {}", summary.ingredients[i]),
+                    );
+                }
+            }
+            if author.contains(term) {
+                found = true;
+                hit(AUTHOR_SCORE, format!("author: {}", metadata.author));
+            }
+
+            if !found {
+                return None;
+            }
+        }
+
+        Some(SearchResult {
+            file: summary.file.clone(),
+            name: metadata.localized(self.language(metadata)).name.to_string(),
+            author: metadata.author.clone(),
+            score,
+            snippets,
+        })
+    }
+
+    /// Search the provided [Summary]s, returning matches ordered by descending score.
+    pub fn search_all<'a, I>(&self, summaries: I) -> Vec<SearchResult>
+    where
+        I: IntoIterator<Item = &'a Summary>,
+    {
+        let mut results: Vec<SearchResult> = summaries
+            .into_iter()
+            .filter_map(|s| self.search(s))
+            .collect();
+
+        results.sort_by(|a, b| match b.score.cmp(&a.score) {
+            Ordering::Equal => a.name.cmp(&b.name),
+            ordering => ordering,
+        });
+        results
+    }
+
+    /// Language to name a matching recipe in.
+    fn language<'a>(&'a self, metadata: &'a Metadata) -> &'a str {
+        self.language
+            .as_deref()
+            .or(metadata.language.as_deref())
+            .unwrap_or(DEFAULT_LANGUAGE)
+    }
+}
+
+/// Split a query into whitespace-separated tokens, keeping quoted sections together.
+fn tokenize(query: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for c in query.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// Split a token such as `total<30m` into its field and the remaining comparison.
+///
+/// A bare field name such as `total` is only treated as a comparison if the next token starts
+/// with a comparison operator.
+fn split_comparison(token: &str, next: Option<&String>) -> Option<(String, String)> {
+    let position = token.find(['<', '>', '=']).unwrap_or(token.len());
+    let (field, rest) = token.split_at(position);
+    let (field, rest) = (field.trim(), rest.trim());
+
+    if !["total", "prep", "cook"].contains(&field.to_lowercase().as_str()) {
+        return None;
+    }
+    if rest.is_empty() && !next.is_some_and(|next| next.starts_with(['<', '>', '='])) {
+        return None;
+    }
+
+    Some((field.to_string(), rest.to_string()))
+}
+
+fn parse_limit(field: &str, comparison: &str) -> Result<TimeLimit, SousError> {
+    let field = match field.to_lowercase().as_str() {
+        "prep" => TimeField::Prep,
+        "cook" => TimeField::Cook,
+        _ => TimeField::Total,
+    };
+
+    let (comparison, value) = [
+        ("<=", Comparison::LessEqual),
+        (">=", Comparison::GreaterEqual),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+        ("=", Comparison::Equal),
+    ]
+    .into_iter()
+    .find_map(|(prefix, c)| comparison.strip_prefix(prefix).map(|v| (c, v)))
+    .ok_or_else(|| SousError::QueryError(format!("invalid comparison '{comparison}'")))?;
+
+    Ok(TimeLimit {
+        field,
+        comparison,
+        minutes: parse_minutes(value)?,
+    })
+}
+
+/// Parse a duration such as `90`, `45m`, `45min`, `1h` or `1h30m` into minutes.
+fn parse_minutes(value: &str) -> Result<u32, SousError> {
+    let invalid = || SousError::QueryError(format!("invalid duration '{value}'"));
+    let value = value.trim().to_lowercase();
+    if value.is_empty() {
+        return Err(SousError::QueryError("missing duration".to_string()));
+    }
+
+    let (hours, minutes) = match value.split_once('h') {
+        Some((hours, minutes)) => (hours, minutes),
+        None => ("0", value.as_str()),
+    };
+    let minutes = minutes.trim_end_matches("min").trim_end_matches('m');
+
+    let hours: u32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: u32 = if minutes.is_empty() {
+        0
+    } else {
+        minutes.parse().map_err(|_| invalid())?
+    };
+
+    hours
+        .checked_mul(60)
+        .and_then(|hours| hours.checked_add(minutes))
+        .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Text;
+
+    fn gen_summary(file: &str, name: &str, ingredients: &[&str], minutes: u32) -> Summary {
+        Summary {
+            file: file.to_string(),
+            metadata: Metadata {
+                name: name.into(),
+                author: "test author".to_string(),
+                cook_minutes: minutes,
+                tags: vec!["quick".to_string()],
+                ..Default::default()
+            },
+            ingredients: ingredients.iter().map(|i| i.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse() -> Result<(), SousError> {
+        let query = Query::parse("curry has: chickpeas not:nuts tag:quick total < 30m cook<= 1h")?;
+
+        assert_eq!(query.terms, vec!["curry"]);
+        assert_eq!(query.has, vec!["chickpeas"]);
+        assert_eq!(query.not, vec!["nuts"]);
+        assert_eq!(query.filter.tags, vec!["quick"]);
+        assert_eq!(
+            query.limits,
+            vec![
+                TimeLimit {
+                    field: TimeField::Total,
+                    comparison: Comparison::Less,
+                    minutes: 30
+                },
+                TimeLimit {
+                    field: TimeField::Cook,
+                    comparison: Comparison::LessEqual,
+                    minutes: 60
+                }
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_quoted() -> Result<(), SousError> {
+        let query = Query::parse("has:\"olive oil\" author:\"test author\" \"total < 30m\"")?;
+
+        assert_eq!(query.has, vec!["olive oil"]);
+        assert_eq!(query.author, vec!["test author"]);
+        assert!(query.terms.is_empty());
+        assert_eq!(
+            query.limits,
+            vec![TimeLimit {
+                field: TimeField::Total,
+                comparison: Comparison::Less,
+                minutes: 30
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Query::parse("colour:red").is_err());
+        assert!(Query::parse("total<soon").is_err());
+        assert!(Query::parse("has:").is_err());
+        assert!(Query::parse("total <").is_err());
+        assert!(Query::parse("total<99999999h").is_err());
+    }
+
+    #[test]
+    fn test_search() -> Result<(), SousError> {
+        let summaries = vec![
+            gen_summary(
+                "a.yml",
+                "Chickpea Curry",
+                &["chickpeas", "coconut milk"],
+                25,
+            ),
+            gen_summary("b.yml", "Hummus", &["chickpeas", "tahini"], 10),
+            gen_summary("c.yml", "Peanut Noodles", &["peanuts", "noodles"], 15),
+        ];
+
+        let results = Query::parse("chickpea")?.search_all(&summaries);
+        let files: Vec<&str> = results.iter().map(|r| r.file.as_str()).collect();
+        assert_eq!(files, vec!["a.yml", "b.yml"]);
+        assert!(results[0].score > results[1].score);
+        assert!(results[1]
+            .snippets
+            .contains(&"ingredient: chickpeas".to_string()));
+
+        let results = Query::parse("not:peanut total<20m")?.search_all(&summaries);
+        let files: Vec<&str> = results.iter().map(|r| r.file.as_str()).collect();
+        assert_eq!(files, vec!["b.yml"]);
+
+        let results = Query::parse("tag:quick")?.search_all(&summaries);
+        assert_eq!(results.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_localized() -> Result<(), SousError> {
+        let mut summary = gen_summary("a.yml", "", &["chickpeas"], 10);
+        summary.metadata.name = Text::Translated(
+            [("de", "A"), ("fr", "B")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        );
+        summary.metadata.language = Some("fr".to_string());
+
+        let mut query = Query::parse("chickpea")?;
+        assert_eq!(query.search(&summary).unwrap().name, "B");
+        query.language = Some("de".to_string());
+        assert_eq!(query.search(&summary).unwrap().name, "A");
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+//! Types for generating static websites from cookbooks.
+
+use std::fs;
+use std::path::Path;
+
+use crate::format::slugify;
+use crate::render::{HtmlRenderer, Renderer};
+use crate::{Cookbook, SousError};
+
+/// Name of the directory of static assets copied from a cookbook into its site.
+pub const STATIC_DIR: &str = "static";
+
+/// Static website generated from a [Cookbook].
+///
+/// A site consists of an `index.html` table of contents, one page per recipe, one page per tag in
+/// a `tags` directory, a `style.css` stylesheet, the theme's static assets, and a copy of the
+/// cookbook's `static` directory if present.
+pub struct Site<'a> {
+    cookbook: &'a Cookbook,
+    renderer: HtmlRenderer,
+}
+
+impl<'a> Site<'a> {
+    /// Create a site for the provided cookbook, rendered with the given [HtmlRenderer].
+    ///
+    /// If the renderer has no title, the cookbook manifest's title is used.
+    pub fn new(cookbook: &'a Cookbook, mut renderer: HtmlRenderer) -> Self {
+        if renderer.title.is_none() {
+            renderer.title = cookbook.manifest().title.clone();
+        }
+
+        Site { cookbook, renderer }
+    }
+
+    /// Write the complete site into the provided output directory, creating it if necessary.
+    pub fn generate(&self, output: &Path) -> Result<(), SousError> {
+        fs::create_dir_all(output.join("tags"))?;
+
+        for file in self.cookbook.recipes() {
+            let recipe = self.cookbook.load_recipe(file)?;
+            let page = self.cookbook.page(file, "html");
+            fs::write(output.join(page), self.renderer.render(&recipe)?)?;
+        }
+
+        let language = self.renderer.language.as_deref();
+        let labels = self.renderer.labels();
+        let index = self.cookbook.index("html", language, &labels)?;
+        if let Some(page) = self.renderer.render_index(&index)? {
+            fs::write(output.join("index.html"), page)?;
+        }
+
+        for tag in self.cookbook.tag_index("html", language, &labels)?.sections {
+            if tag.entries.iter().all(|entry| entry.tags.is_empty()) {
+                continue;
+            }
+            if let Some(rendered) = self.renderer.render_tag(&tag)? {
+                let page = format!("{}.html", slugify(&tag.title));
+                fs::write(output.join("tags").join(page), rendered)?;
+            }
+        }
+
+        fs::write(output.join("style.css"), self.renderer.render_stylesheet()?)?;
+
+        for (name, source) in self.renderer.assets() {
+            let target = output.join(name);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(source, target)?;
+        }
+
+        let assets = self.cookbook.path().join(STATIC_DIR);
+        if assets.is_dir() {
+            copy_dir(&assets, &output.join(STATIC_DIR))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<(), SousError> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::temp_dir;
+
+    #[test]
+    fn test_generate() -> Result<(), SousError> {
+        let dir = temp_dir("site");
+        let (cookbook_dir, output) = (dir.join("cookbook"), dir.join("site"));
+        fs::create_dir_all(cookbook_dir.join(STATIC_DIR))?;
+
+        fs::write(
+            cookbook_dir.join("pie.yml"),
+            "name: Pie\nauthor: test\nservings: 1\ncook_minutes: 1\ntags: [Sweet Things]\nsteps: []\ningredients:\n  - name: crust\n    recipe: crust",
+        )?;
+        fs::write(
+            cookbook_dir.join("crust.yml"),
+            "name: Crust\nauthor: test\nservings: 1\ncook_minutes: 1\nsteps: []\ningredients: []",
+        )?;
+        fs::write(cookbook_dir.join(STATIC_DIR).join("logo.svg"), "<svg/>")?;
+
+        let theme = dir.join("theme");
+        fs::create_dir_all(theme.join("fonts"))?;
+        fs::write(theme.join("style.css"), "body { color: {{ red }}; }")?;
+        fs::write(theme.join("fonts").join("serif.woff2"), [0xff, 0xfe])?;
+
+        let cookbook = Cookbook::open(&cookbook_dir)?;
+        let renderer = HtmlRenderer::new()?.with_theme(&theme)?;
+        Site::new(&cookbook, renderer).generate(&output)?;
+
+        let pie = fs::read_to_string(output.join("pie.html"))?;
+        assert!(pie.contains("<a href=\"crust.html\">crust</a>"));
+        assert!(output.join("crust.html").is_file());
+        assert!(output.join("index.html").is_file());
+        assert!(output.join("tags").join("sweet-things.html").is_file());
+        assert!(!output.join("tags").join("untagged.html").exists());
+        assert_eq!(
+            fs::read_to_string(output.join("style.css"))?,
+            "body { color: {{ red }}; }"
+        );
+        assert!(output.join("fonts").join("serif.woff2").is_file());
+        assert!(output.join(STATIC_DIR).join("logo.svg").is_file());
+
+        Ok(())
+    }
+}
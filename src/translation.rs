@@ -0,0 +1,158 @@
+//! Types for translatable recipe text.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Language assumed for recipes that do not declare one.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Recipe text that is either written in a single language or translated into several.
+///
+/// In YAML, text is given either as a plain string or as a mapping of language codes to
+/// translations:
+///
+/// ```yaml
+/// name:
+///   en: Pancakes
+///   de: Pfannkuchen
+/// ```
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(untagged)]
+#[schemars(
+    description = "Text in the recipe's language, or a mapping of language codes to translations."
+)]
+pub enum Text {
+    /// Text in the recipe's default language.
+    Plain(String),
+    /// Translations keyed by language code (e.g. `en` or `de`).
+    Translated(BTreeMap<String, String>),
+}
+
+impl Text {
+    /// Create new, empty text.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Get the text in `language`, falling back to the `fallback` language and then to any
+    /// available translation. Plain text is returned as is.
+    pub fn get(&self, language: &str, fallback: &str) -> &str {
+        match self {
+            Text::Plain(text) => text,
+            Text::Translated(translations) => translations
+                .get(language)
+                .or_else(|| translations.get(fallback))
+                .or_else(|| translations.values().next())
+                .map(String::as_str)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Get the text in the [DEFAULT_LANGUAGE], or any available translation.
+    pub fn as_str(&self) -> &str {
+        self.get(DEFAULT_LANGUAGE, DEFAULT_LANGUAGE)
+    }
+
+    /// Resolve the text into plain text in `language`, as described by [Text::get].
+    pub fn localized(&self, language: &str, fallback: &str) -> Text {
+        Text::Plain(self.get(language, fallback).to_string())
+    }
+
+    /// Language codes the text is translated into. Plain text has no translations.
+    pub fn languages(&self) -> Vec<&str> {
+        match self {
+            Text::Plain(_) => Vec::new(),
+            Text::Translated(translations) => translations.keys().map(String::as_str).collect(),
+        }
+    }
+
+    /// Every version of the text, in all available languages.
+    pub fn values(&self) -> Vec<&str> {
+        match self {
+            Text::Plain(text) => vec![text],
+            Text::Translated(translations) => translations.values().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl Default for Text {
+    fn default() -> Self {
+        Text::Plain(String::new())
+    }
+}
+
+impl From<&str> for Text {
+    fn from(text: &str) -> Self {
+        Text::Plain(text.to_string())
+    }
+}
+
+impl From<String> for Text {
+    fn from(text: String) -> Self {
+        Text::Plain(text)
+    }
+}
+
+impl PartialEq<str> for Text {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Text {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for Text {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gen_text() -> Text {
+        serde_yaml::from_str("{en: Pancakes, de: Pfannkuchen}").unwrap()
+    }
+
+    #[test]
+    fn test_deserialize() {
+        assert_eq!(
+            serde_yaml::from_str::<Text>("Pancakes").unwrap(),
+            Text::from("Pancakes")
+        );
+        assert_eq!(gen_text().languages(), vec!["de", "en"]);
+    }
+
+    #[test]
+    fn test_get() {
+        let text = gen_text();
+
+        assert_eq!(text.get("de", "en"), "Pfannkuchen");
+        assert_eq!(text.get("fr", "en"), "Pancakes");
+        assert_eq!(text.get("fr", "it"), "Pfannkuchen");
+        assert_eq!(text.as_str(), "Pancakes");
+        assert_eq!(Text::from("Crêpes").get("de", "en"), "Crêpes");
+    }
+
+    #[test]
+    fn test_localized() {
+        assert_eq!(
+            gen_text().localized("de", "en"),
+            Text::Plain("Pfannkuchen".to_string())
+        );
+        assert_eq!(
+            serde_yaml::to_string(&gen_text().localized("de", "en")).unwrap(),
+            "Pfannkuchen\n"
+        );
+    }
+}
@@ -0,0 +1,357 @@
+//! Types for converting ingredient amounts between systems of measurement.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::SousError;
+
+/// A system of measurement that ingredient amounts can be converted into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitSystem {
+    /// Grams, kilograms, milliliters and liters.
+    Metric,
+    /// Ounces, pounds, teaspoons, tablespoons and cups.
+    Imperial,
+}
+
+impl UnitSystem {
+    /// Convert an amount given in `unit` into the most fitting unit of this system.
+    ///
+    /// Returns the converted amount and the canonical name of the new unit, or [None] if the unit
+    /// is not recognized. Amounts already expressed in this system, or too small to be written in
+    /// its units (e.g. a pinch of 0.2 g in ounces), are returned unchanged.
+    pub fn convert(&self, amount: f32, unit: &str) -> Option<(f32, &'static str)> {
+        let from = Unit::find(unit)?;
+        if from.system == *self {
+            return Some((amount, from.name));
+        }
+
+        let base = amount * from.factor;
+        let candidates: Vec<&Unit> = UNITS
+            .iter()
+            .filter(|u| u.system == *self && u.dimension == from.dimension && u.target)
+            .collect();
+
+        let to = candidates
+            .iter()
+            .rev()
+            .find(|u| base.abs() / u.factor >= 1.0)
+            .or_else(|| candidates.first())?;
+        if amount != 0.0 && base.abs() / to.factor < self.minimum() {
+            return Some((amount, from.name));
+        }
+
+        Some((to.round(base / to.factor), to.name))
+    }
+
+    /// Smallest amount written in the units of this system.
+    fn minimum(&self) -> f32 {
+        match self {
+            UnitSystem::Metric => 1.0,
+            UnitSystem::Imperial => 0.125,
+        }
+    }
+}
+
+/// Convert an amount given in unit `from` into unit `to`, which must measure the same dimension
+/// (mass or volume).
+///
+/// Returns the converted amount and the canonical name of `to`, or [None] if either unit is not
+/// recognized or the units are incompatible.
+pub fn convert_unit(amount: f32, from: &str, to: &str) -> Option<(f32, &'static str)> {
+    let (from, to) = (Unit::find(from)?, Unit::find(to)?);
+    if from.dimension != to.dimension {
+        return None;
+    }
+
+    Some((to.round(amount * from.factor / to.factor), to.name))
+}
+
+/// Pluralize a unit written out as a word (e.g. "cup" becomes "cups") when the amount is greater
+/// than one. Abbreviations and unrecognized units are returned unchanged.
+pub fn pluralize_unit(unit: &str, amount: f32) -> String {
+    if amount <= 1.0 || unit.len() <= 2 {
+        return unit.to_string();
+    }
+
+    let plural = format!("{unit}s");
+    match Unit::find(unit) {
+        Some(known) if known.aliases.contains(&plural.to_lowercase().as_str()) => plural,
+        _ => unit.to_string(),
+    }
+}
+
+/// Write a recognized unit by its canonical name (e.g. "Tablespoons" becomes "tbsp").
+/// Unrecognized units are returned with surrounding whitespace removed.
+pub fn normalize_unit(unit: &str) -> String {
+    match Unit::find(unit) {
+        Some(known) => known.name.to_string(),
+        None => unit.trim().to_string(),
+    }
+}
+
+/// Get the canonical name of a recognized unit (e.g. "tbsp" for "Tablespoons").
+pub(crate) fn known_unit(unit: &str) -> Option<&'static str> {
+    Unit::find(unit).map(|known| known.name)
+}
+
+impl fmt::Display for UnitSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitSystem::Metric => write!(f, "metric"),
+            UnitSystem::Imperial => write!(f, "imperial"),
+        }
+    }
+}
+
+impl FromStr for UnitSystem {
+    type Err = SousError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "metric" => Ok(UnitSystem::Metric),
+            "imperial" | "us" => Ok(UnitSystem::Imperial),
+            _ => Err(SousError::UnknownUnitSystem(s.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Dimension {
+    Mass,
+    Volume,
+}
+
+/// A known unit of measurement, with its size relative to grams or milliliters.
+struct Unit {
+    name: &'static str,
+    aliases: &'static [&'static str],
+    dimension: Dimension,
+    system: UnitSystem,
+    factor: f32,
+    /// Whether amounts may be converted into this unit.
+    target: bool,
+}
+
+impl Unit {
+    fn find(name: &str) -> Option<&'static Unit> {
+        let name = name.trim().to_lowercase();
+        UNITS
+            .iter()
+            .find(|u| u.name == name || u.aliases.contains(&name.as_str()))
+    }
+
+    /// Round an amount of this unit to a precision fitting its size: whole grams and milliliters
+    /// from 10 up, eighths of imperial units from 1/8 up, and three significant digits otherwise.
+    fn round(&self, amount: f32) -> f32 {
+        let magnitude = amount.abs();
+        match self.system {
+            _ if magnitude == 0.0 => 0.0,
+            UnitSystem::Metric if self.factor == 1.0 && magnitude >= 10.0 => amount.round(),
+            UnitSystem::Imperial if magnitude >= 0.125 => (amount * 8.0).round() / 8.0,
+            _ => {
+                let scale = 10f32.powi(2 - magnitude.log10().floor() as i32);
+                (amount * scale).round() / scale
+            }
+        }
+    }
+}
+
+const UNITS: &[Unit] = &[
+    Unit {
+        name: "g",
+        aliases: &["gram", "grams"],
+        dimension: Dimension::Mass,
+        system: UnitSystem::Metric,
+        factor: 1.0,
+        target: true,
+    },
+    Unit {
+        name: "kg",
+        aliases: &["kilogram", "kilograms"],
+        dimension: Dimension::Mass,
+        system: UnitSystem::Metric,
+        factor: 1000.0,
+        target: true,
+    },
+    Unit {
+        name: "ml",
+        aliases: &["milliliter", "milliliters", "millilitre", "millilitres"],
+        dimension: Dimension::Volume,
+        system: UnitSystem::Metric,
+        factor: 1.0,
+        target: true,
+    },
+    Unit {
+        name: "l",
+        aliases: &["liter", "liters", "litre", "litres"],
+        dimension: Dimension::Volume,
+        system: UnitSystem::Metric,
+        factor: 1000.0,
+        target: true,
+    },
+    Unit {
+        name: "oz",
+        aliases: &["ounce", "ounces"],
+        dimension: Dimension::Mass,
+        system: UnitSystem::Imperial,
+        factor: 28.349_524,
+        target: true,
+    },
+    Unit {
+        name: "lb",
+        aliases: &["lbs", "pound", "pounds"],
+        dimension: Dimension::Mass,
+        system: UnitSystem::Imperial,
+        factor: 453.592_37,
+        target: true,
+    },
+    Unit {
+        name: "tsp",
+        aliases: &["teaspoon", "teaspoons"],
+        dimension: Dimension::Volume,
+        system: UnitSystem::Imperial,
+        factor: 4.928_922,
+        target: true,
+    },
+    Unit {
+        name: "tbsp",
+        aliases: &["tablespoon", "tablespoons"],
+        dimension: Dimension::Volume,
+        system: UnitSystem::Imperial,
+        factor: 14.786_765,
+        target: true,
+    },
+    Unit {
+        name: "fl oz",
+        aliases: &["fluid ounce", "fluid ounces"],
+        dimension: Dimension::Volume,
+        system: UnitSystem::Imperial,
+        factor: 29.573_53,
+        target: false,
+    },
+    Unit {
+        name: "cup",
+        aliases: &["cups", "c"],
+        dimension: Dimension::Volume,
+        system: UnitSystem::Imperial,
+        factor: 236.588_24,
+        target: true,
+    },
+    Unit {
+        name: "pint",
+        aliases: &["pints", "pt"],
+        dimension: Dimension::Volume,
+        system: UnitSystem::Imperial,
+        factor: 473.176_48,
+        target: false,
+    },
+    Unit {
+        name: "quart",
+        aliases: &["quarts", "qt"],
+        dimension: Dimension::Volume,
+        system: UnitSystem::Imperial,
+        factor: 946.352_96,
+        target: false,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_to_metric() {
+        assert_eq!(UnitSystem::Metric.convert(1.0, "cup"), Some((237.0, "ml")));
+        assert_eq!(UnitSystem::Metric.convert(1.0, "tsp"), Some((4.93, "ml")));
+        assert_eq!(
+            UnitSystem::Metric.convert(4.0, "pounds"),
+            Some((1.81, "kg"))
+        );
+    }
+
+    #[test]
+    fn test_convert_to_imperial() {
+        assert_eq!(
+            UnitSystem::Imperial.convert(500.0, "g"),
+            Some((1.125, "lb"))
+        );
+        assert_eq!(
+            UnitSystem::Imperial.convert(15.0, "ml"),
+            Some((1.0, "tbsp"))
+        );
+        assert_eq!(UnitSystem::Imperial.convert(1.0, "l"), Some((4.25, "cup")));
+    }
+
+    #[test]
+    fn test_convert_zero() {
+        assert_eq!(UnitSystem::Metric.convert(0.0, "cup"), Some((0.0, "ml")));
+        assert_eq!(UnitSystem::Imperial.convert(0.0, "g"), Some((0.0, "oz")));
+    }
+
+    #[test]
+    fn test_convert_tiny() {
+        assert_eq!(UnitSystem::Imperial.convert(0.2, "g"), Some((0.2, "g")));
+        assert_eq!(UnitSystem::Metric.convert(0.01, "tsp"), Some((0.01, "tsp")));
+        assert_eq!(
+            UnitSystem::Metric.convert(0.001, "cup"),
+            Some((0.001, "cup"))
+        );
+        assert_eq!(UnitSystem::Imperial.convert(4.0, "g"), Some((0.125, "oz")));
+        assert_eq!(convert_unit(0.01, "tsp", "ml"), Some((0.0493, "ml")));
+        assert_eq!(convert_unit(0.2, "g", "oz"), Some((0.00705, "oz")));
+    }
+
+    #[test]
+    fn test_convert_negative() {
+        assert_eq!(
+            UnitSystem::Metric.convert(-1.0, "cup"),
+            Some((-237.0, "ml"))
+        );
+        assert_eq!(
+            UnitSystem::Imperial.convert(-500.0, "g"),
+            Some((-1.125, "lb"))
+        );
+        assert_eq!(UnitSystem::Imperial.convert(-0.2, "g"), Some((-0.2, "g")));
+        assert_eq!(convert_unit(-0.01, "tsp", "ml"), Some((-0.0493, "ml")));
+    }
+
+    #[test]
+    fn test_convert_same_system() {
+        assert_eq!(UnitSystem::Metric.convert(2.0, "Grams"), Some((2.0, "g")));
+    }
+
+    #[test]
+    fn test_convert_unknown() {
+        assert_eq!(UnitSystem::Metric.convert(1.0, "pinch"), None);
+    }
+
+    #[test]
+    fn test_convert_unit() {
+        assert_eq!(convert_unit(2.0, "cups", "ml"), Some((473.0, "ml")));
+        assert_eq!(convert_unit(3.0, "tsp", "tablespoon"), Some((1.0, "tbsp")));
+        assert_eq!(convert_unit(1.0, "cup", "g"), None);
+        assert_eq!(convert_unit(1.0, "pinch", "g"), None);
+    }
+
+    #[test]
+    fn test_normalize_unit() {
+        assert_eq!(normalize_unit("Tablespoons"), "tbsp");
+        assert_eq!(normalize_unit("cups"), "cup");
+        assert_eq!(normalize_unit(" pinch "), "pinch");
+    }
+
+    #[test]
+    fn test_pluralize_unit() {
+        assert_eq!(pluralize_unit("cup", 2.0), "cups");
+        assert_eq!(pluralize_unit("Tablespoon", 1.5), "Tablespoons");
+        assert_eq!(pluralize_unit("cup", 1.0), "cup");
+        assert_eq!(pluralize_unit("cups", 2.0), "cups");
+        assert_eq!(pluralize_unit("tbsp", 2.0), "tbsp");
+        assert_eq!(pluralize_unit("lb", 2.0), "lb");
+        assert_eq!(pluralize_unit("pinch", 2.0), "pinch");
+    }
+}